@@ -0,0 +1,301 @@
+//! Benchmarks for the algorithms in the crate, printed as CSV on stdout
+//!
+//! Run with `cargo bench`, or `cargo bench -- merge binary` to only run the benchmarks whose
+//! `benchmark/algorithm` name contains one of the arguments. Every row is one input shape and
+//! size, with the mean wall-clock time of a single run in nanoseconds.
+
+#![allow(clippy::needless_return)]
+
+use std::env;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use algorithms_exercises::array_list::ArrayList;
+use algorithms_exercises::avl_tree::AVLTree;
+use algorithms_exercises::binary_search::binary_search;
+use algorithms_exercises::binary_search_tree::BinarySearchTree;
+use algorithms_exercises::linked_list::LinkedList;
+use algorithms_exercises::sorter::{integer_sorters, string_sorters, BucketSort, CountingSort, QuickSortInPlace, RadixSort, Sorter};
+use algorithms_exercises::static_search_index::StaticSearchIndex;
+use algorithms_exercises::test_support::{generate, Distribution, Rng};
+
+const SIZES: [usize; 6] = [10, 100, 1_000, 10_000, 100_000, 1_000_000];
+
+/// Searches also run on an input that is far larger than the cache
+const SEARCH_SIZES: [usize; 7] = [10, 100, 1_000, 10_000, 100_000, 1_000_000, 10_000_000];
+
+/// Sorts with a quadratic worst case (and the O(n) per operation lists) stop at this size, even
+/// the quick sorts, whose worst case shows up on the sorted and few unique inputs
+const QUADRATIC_MAX_SIZE: usize = 10_000;
+
+/// Each case is repeated until it has run for at least this long
+const TARGET_TIME: Duration = Duration::from_millis(50);
+
+const MAX_ITERATIONS: usize = 10_000;
+
+struct Bench {
+    filters: Vec<String>,
+}
+
+impl Bench {
+    fn new() -> Self {
+        let filters = env::args().skip(1).filter(|arg| !arg.starts_with("--")).collect();
+
+        println!("benchmark,algorithm,distribution,size,iterations,ns_per_iter");
+
+        return Self { filters };
+    }
+
+    fn enabled(&self, benchmark: &str, algorithm: &str) -> bool {
+        let name = format!("{}/{}", benchmark, algorithm);
+
+        return self.filters.is_empty() || self.filters.iter().any(|filter| name.contains(filter.as_str()));
+    }
+
+    /// Times `run` on a fresh value from `setup` until [`TARGET_TIME`] is reached, `setup`
+    /// itself is not timed
+    fn run<S, I, R, O>(&self, row: (&str, &str, &str, usize), mut setup: S, mut run: R)
+    where
+        S: FnMut() -> I,
+        R: FnMut(I) -> O,
+    {
+        let (benchmark, algorithm, distribution, size) = row;
+        let mut elapsed = Duration::ZERO;
+        let mut iterations = 0;
+
+        while elapsed < TARGET_TIME && iterations < MAX_ITERATIONS {
+            let input = setup();
+            let start = Instant::now();
+
+            black_box(run(black_box(input)));
+            elapsed += start.elapsed();
+            iterations += 1;
+        }
+
+        println!(
+            "{},{},{},{},{},{}",
+            benchmark,
+            algorithm,
+            distribution,
+            size,
+            iterations,
+            elapsed.as_nanos() / iterations as u128
+        );
+    }
+}
+
+fn bench_sorts(bench: &Bench) {
+    for sorter in integer_sorters::<u64>() {
+        if !bench.enabled("sort", sorter.name()) {
+            continue;
+        }
+
+        for distribution in Distribution::ALL {
+            for size in SIZES {
+                if sorter.has_quadratic_worst_case() && size > QUADRATIC_MAX_SIZE {
+                    continue;
+                }
+
+                let input = generate(distribution, size, 0);
+
+                bench.run(
+                    ("sort", sorter.name(), &distribution.to_string(), size),
+                    || input.clone(),
+                    |mut arr| {
+                        sorter.sort(&mut arr);
+
+                        return arr;
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Sorts of byte-string keys, which include the MSD radix sorts
+///
+/// The keys are the integer inputs written as fixed-width hex, so they sort in the same order and
+/// the sorted and few unique shapes share long prefixes.
+fn bench_string_sorts(bench: &Bench) {
+    for sorter in string_sorters::<String>() {
+        if !bench.enabled("string_sort", sorter.name()) {
+            continue;
+        }
+
+        for distribution in Distribution::ALL {
+            for size in SIZES {
+                if sorter.has_quadratic_worst_case() && size > QUADRATIC_MAX_SIZE {
+                    continue;
+                }
+
+                let input: Vec<String> = generate(distribution, size, 0).iter().map(|key| format!("{:016x}", key)).collect();
+
+                bench.run(
+                    ("string_sort", sorter.name(), &distribution.to_string(), size),
+                    || input.clone(),
+                    |mut arr| {
+                        sorter.sort(&mut arr);
+
+                        return arr;
+                    },
+                );
+            }
+        }
+    }
+}
+
+/// Sorts that rely on the distribution of the keys, next to the general purpose ones
+fn bench_linear_sorts(bench: &Bench) {
+    let small_key_sorters: [&dyn Sorter<u64>; 3] = [&CountingSort, &RadixSort, &QuickSortInPlace];
+    let float_sorters: [&dyn Sorter<f64>; 3] = [&BucketSort, &RadixSort, &QuickSortInPlace];
+
+    for size in SIZES {
+        let random = generate(Distribution::Random, size, 0);
+        let small_keys: Vec<u64> = random.iter().map(|key| key % 1024).collect();
+        let uniform: Vec<f64> = random.iter().map(|&key| key as f64 / u64::MAX as f64).collect();
+
+        for sorter in small_key_sorters {
+            if bench.enabled("small_key_sort", sorter.name()) {
+                bench.run(
+                    ("small_key_sort", sorter.name(), "random_below_1024", size),
+                    || small_keys.clone(),
+                    |mut arr| {
+                        sorter.sort(&mut arr);
+
+                        return arr;
+                    },
+                );
+            }
+        }
+
+        for sorter in float_sorters {
+            if bench.enabled("float_sort", sorter.name()) {
+                bench.run(
+                    ("float_sort", sorter.name(), "uniform", size),
+                    || uniform.clone(),
+                    |mut arr| {
+                        sorter.sort(&mut arr);
+
+                        return arr;
+                    },
+                );
+            }
+        }
+    }
+}
+
+fn bench_searches(bench: &Bench) {
+    for size in SEARCH_SIZES {
+        let arr: Vec<isize> = (0..size as isize).map(|n| n * 2).collect();
+        let mut rng = Rng::new(0);
+        let targets: Vec<isize> = (0..1000).map(|_| arr[rng.below(size as u64) as usize]).collect();
+
+        if bench.enabled("search", "binary_search") {
+            bench.run(
+                ("search", "binary_search", "random_hits_x1000", size),
+                || (),
+                |_| targets.iter().filter(|target| binary_search(&arr, target).is_ok()).count(),
+            );
+        }
+
+        for (name, prefetch) in [("static_search_index", false), ("static_search_index_prefetch", true)] {
+            if bench.enabled("search", name) {
+                let index = StaticSearchIndex::new(&arr, prefetch);
+
+                bench.run(
+                    ("search", name, "random_hits_x1000", size),
+                    || (),
+                    |_| targets.iter().filter(|target| index.contains(target)).count(),
+                );
+            }
+        }
+    }
+}
+
+fn bench_trees(bench: &Bench) {
+    for size in SIZES {
+        // Trees are built from random values, sorted input degenerates the plain BST into a list
+        let values = generate(Distribution::Random, size, 0);
+
+        if bench.enabled("tree", "binary_search_tree_insert") {
+            bench.run(
+                ("tree", "binary_search_tree_insert", "random", size),
+                || values.clone(),
+                BinarySearchTree::new,
+            );
+        }
+
+        if bench.enabled("tree", "binary_search_tree_find") {
+            let tree = BinarySearchTree::new(values.clone());
+
+            bench.run(
+                ("tree", "binary_search_tree_find", "random", size),
+                || (),
+                |_| values.iter().filter(|value| tree.find(value)).count(),
+            );
+        }
+
+        if bench.enabled("tree", "avl_tree_insert") {
+            bench.run(
+                ("tree", "avl_tree_insert", "random", size),
+                || values.clone(),
+                AVLTree::new,
+            );
+        }
+
+        if bench.enabled("tree", "avl_tree_find") {
+            let tree = AVLTree::new(values.clone());
+
+            bench.run(
+                ("tree", "avl_tree_find", "random", size),
+                || (),
+                |_| values.iter().filter(|value| tree.find(value)).count(),
+            );
+        }
+    }
+}
+
+fn bench_lists(bench: &Bench) {
+    for size in SIZES.into_iter().filter(|&size| size <= QUADRATIC_MAX_SIZE) {
+        if bench.enabled("list", "linked_list_push_pop") {
+            bench.run(("list", "linked_list_push_pop", "sequential", size), || (), |_| {
+                let mut list = LinkedList::new();
+
+                for value in 0..size {
+                    list.push(value);
+                }
+
+                while list.pop().is_some() {}
+
+                return list;
+            });
+        }
+
+        if bench.enabled("list", "array_list_push_pop") {
+            bench.run(("list", "array_list_push_pop", "sequential", size), || (), |_| {
+                let mut list = ArrayList::new();
+
+                for value in 0..size {
+                    list.push(value);
+                }
+
+                for _ in 0..size {
+                    list.pop();
+                }
+
+                return list;
+            });
+        }
+    }
+}
+
+fn main() {
+    let bench = Bench::new();
+
+    bench_sorts(&bench);
+    bench_string_sorts(&bench);
+    bench_linear_sorts(&bench);
+    bench_searches(&bench);
+    bench_trees(&bench);
+    bench_lists(&bench);
+}
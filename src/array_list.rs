@@ -1,3 +1,6 @@
+// `ArrayList::new` takes no arguments but the type has no `Default`
+#![allow(clippy::new_without_default)]
+
 use std::collections::HashMap;
 
 pub struct ArrayList {
@@ -1,3 +1,6 @@
+// The tree's doc comment is separated from the struct it was written for
+#![allow(clippy::empty_line_after_doc_comments)]
+
 use std::cmp::{max, Ordering};
 
 /// AVL tree implementation
@@ -151,6 +154,20 @@ impl<T: Ord + Clone> AVLTree<T> {
             None => self.root = Some(Box::new(Node::new(value, None, None)))
         }
     }
+
+    pub fn find(&self, value: &T) -> bool {
+        let mut current = &self.root;
+
+        while let Some(ref node) = current {
+            match value.cmp(&node.value) {
+                Ordering::Less => { current = &node.left; }
+                Ordering::Greater => { current = &node.right; }
+                Ordering::Equal => return true
+            }
+        }
+
+        return false;
+    }
 }
 
 #[cfg(test)]
@@ -479,4 +496,17 @@ mod tests {
                 .right.as_ref().is_none()
         );
     }
+
+    #[test]
+    fn test_find() {
+        let avl_tree = AVLTree::new(Vec::from([3, 7, 4, 6, 5, 1, 10, 2, 9, 8]));
+
+        for value in 1..=10 {
+            assert!(avl_tree.find(&value));
+        }
+
+        assert!(!avl_tree.find(&0));
+        assert!(!avl_tree.find(&11));
+        assert!(!AVLTree::new(Vec::new()).find(&1));
+    }
 }
@@ -0,0 +1,461 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+use crate::nested_add::Accumulator;
+
+/// Largest power of 10 that fits in a limb, [`Display`](fmt::Display) peels off this many
+/// digits per division
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+/// Arbitrary-precision unsigned integer
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::big_int::BigUint;
+///
+/// let big = BigUint::from(u64::MAX) * BigUint::from(u64::MAX);
+///
+/// assert_eq!(big.to_string(), "340282366920938463426481119284349108225");
+/// assert_eq!(big.div_rem_small(10), (BigUint::from(34028236692093846342648111928434910822u128), 5));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Base 2^32 digits, least significant first, without trailing zeros so that zero is empty
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        return Self { limbs: Vec::new() };
+    }
+
+    pub fn is_zero(&self) -> bool {
+        return self.limbs.is_empty();
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        return Self { limbs };
+    }
+
+    /// `self * factor`, in O(n) instead of the O(n·m) of a full multiplication
+    pub fn mul_small(&self, factor: u32) -> Self {
+        let mut carry = 0;
+        let mut limbs: Vec<u32> = self
+            .limbs
+            .iter()
+            .map(|&limb| {
+                let product = limb as u64 * factor as u64 + carry;
+
+                carry = product >> 32;
+
+                return product as u32;
+            })
+            .collect();
+
+        limbs.push(carry as u32);
+
+        return Self::from_limbs(limbs);
+    }
+
+    /// Quotient and remainder of `self / divisor`
+    ///
+    /// # Panics
+    /// When `divisor` is 0.
+    pub fn div_rem_small(&self, divisor: u32) -> (Self, u32) {
+        assert!(divisor != 0, "attempt to divide by zero");
+
+        let mut remainder = 0;
+        let mut limbs = vec![0; self.limbs.len()];
+
+        for (i, &limb) in self.limbs.iter().enumerate().rev() {
+            let dividend = (remainder << 32) | limb as u64;
+
+            limbs[i] = (dividend / divisor as u64) as u32;
+            remainder = dividend % divisor as u64;
+        }
+
+        return (Self::from_limbs(limbs), remainder as u32);
+    }
+}
+
+macro_rules! impl_from_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for BigUint {
+                fn from(value: $t) -> Self {
+                    let mut value = value as u128;
+                    let mut limbs = Vec::new();
+
+                    while value > 0 {
+                        limbs.push(value as u32);
+                        value >>= 32;
+                    }
+
+                    return Self { limbs };
+                }
+            }
+        )*
+    };
+}
+
+impl_from_unsigned!(u8, u16, u32, u64, u128, usize);
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Without trailing zeros the number with more limbs is the larger one
+        return self
+            .limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()));
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+/// Limbs of the sum of two numbers, possibly with a trailing zero
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut carry = 0;
+    let mut limbs: Vec<u32> = long
+        .iter()
+        .enumerate()
+        .map(|(i, &limb)| {
+            let sum = limb as u64 + *short.get(i).unwrap_or(&0) as u64 + carry;
+
+            carry = sum >> 32;
+
+            return sum as u32;
+        })
+        .collect();
+
+    limbs.push(carry as u32);
+
+    return limbs;
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: &BigUint) -> BigUint {
+        return BigUint::from_limbs(add_limbs(&self.limbs, &rhs.limbs));
+    }
+}
+
+impl Add for BigUint {
+    type Output = BigUint;
+
+    fn add(self, rhs: BigUint) -> BigUint {
+        return &self + &rhs;
+    }
+}
+
+/// Operands with fewer limbs than this are multiplied with the schoolbook method, which is
+/// faster than Karatsuba's for small numbers
+const KARATSUBA_CUTOFF: usize = 32;
+
+/// Product of `a` and `b` in O(n·m), possibly with trailing zeros
+fn schoolbook_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut limbs = vec![0u32; a.len() + b.len()];
+
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0;
+
+        for (j, &y) in b.iter().enumerate() {
+            // At most (2^32 - 1)^2 + 2 · (2^32 - 1), which still fits in a u64
+            let product = x as u64 * y as u64 + limbs[i + j] as u64 + carry;
+
+            limbs[i + j] = product as u32;
+            carry = product >> 32;
+        }
+
+        limbs[i + b.len()] = carry as u32;
+    }
+
+    return limbs;
+}
+
+/// Adds `b` to `a` in place, `a` must be long enough to hold the sum
+fn add_into(a: &mut [u32], b: &[u32]) {
+    let mut carry = 0;
+    let mut i = 0;
+
+    while i < b.len() || carry > 0 {
+        let sum = a[i] as u64 + *b.get(i).unwrap_or(&0) as u64 + carry;
+
+        a[i] = sum as u32;
+        carry = sum >> 32;
+        i += 1;
+    }
+}
+
+/// Subtracts `b` from `a` in place, `a` must not be less than `b`
+fn sub_from(a: &mut [u32], b: &[u32]) {
+    let mut borrow = 0;
+    let mut i = 0;
+
+    while i < b.len() || borrow > 0 {
+        let difference = a[i] as i64 - *b.get(i).unwrap_or(&0) as i64 - borrow;
+
+        a[i] = difference.rem_euclid(1 << 32) as u32;
+        borrow = (difference < 0) as i64;
+        i += 1;
+    }
+}
+
+/// `limbs` without its trailing zeros
+fn trimmed(limbs: &[u32]) -> &[u32] {
+    let len = limbs.iter().rposition(|&limb| limb != 0).map_or(0, |i| i + 1);
+
+    return &limbs[..len];
+}
+
+/// Product of `a` and `b`, possibly with trailing zeros
+///
+/// Karatsuba's method splits both numbers in halves, `a = a1·B + a0` and `b = b1·B + b0`, and
+/// gets the middle term `a1·b0 + a0·b1` from `(a0 + a1)(b0 + b1) - a0·b0 - a1·b1`. Three half
+/// size multiplications instead of four make it O(n^1.585) instead of O(n²).
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (trimmed(a), trimmed(b)) } else { (trimmed(b), trimmed(a)) };
+
+    if short.len() < KARATSUBA_CUTOFF {
+        return schoolbook_mul(long, short);
+    }
+
+    let mut limbs = vec![0u32; long.len() + short.len()];
+
+    // Splitting lopsided operands in halves would leave the upper half of `short` empty, so
+    // multiply `short` by every `short`-sized chunk of `long` instead
+    if short.len() * 2 <= long.len() {
+        for (i, chunk) in long.chunks(short.len()).enumerate() {
+            add_into(&mut limbs[i * short.len()..], trimmed(&mul_limbs(chunk, short)));
+        }
+
+        return limbs;
+    }
+
+    let half = long.len() / 2;
+    let (a0, a1) = long.split_at(half);
+    let (b0, b1) = short.split_at(half);
+    let low = mul_limbs(a0, b0);
+    let high = mul_limbs(a1, b1);
+    let mut middle = mul_limbs(&add_limbs(a0, a1), &add_limbs(b0, b1));
+
+    sub_from(&mut middle, trimmed(&low));
+    sub_from(&mut middle, trimmed(&high));
+
+    add_into(&mut limbs, trimmed(&low));
+    add_into(&mut limbs[half..], trimmed(&middle));
+    add_into(&mut limbs[2 * half..], trimmed(&high));
+
+    return limbs;
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// Schoolbook multiplication for small numbers and Karatsuba's method above 32 limbs
+    fn mul(self, rhs: &BigUint) -> BigUint {
+        return BigUint::from_limbs(mul_limbs(&self.limbs, &rhs.limbs));
+    }
+}
+
+impl Mul for BigUint {
+    type Output = BigUint;
+
+    fn mul(self, rhs: BigUint) -> BigUint {
+        return &self * &rhs;
+    }
+}
+
+impl fmt::Display for BigUint {
+    /// Peels off nine decimal digits at a time by dividing by 10^9, every division takes time
+    /// proportional to the length of the number, so the conversion is quadratic
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut chunks = Vec::new();
+        let mut rest = self.clone();
+
+        while !rest.is_zero() {
+            let (quotient, chunk) = rest.div_rem_small(DECIMAL_CHUNK);
+
+            chunks.push(chunk);
+            rest = quotient;
+        }
+
+        let mut digits = chunks.last().map_or(String::from("0"), |chunk| chunk.to_string());
+
+        for chunk in chunks.iter().rev().skip(1) {
+            digits.push_str(&format!("{:0width$}", chunk, width = DECIMAL_CHUNK_DIGITS));
+        }
+
+        return f.pad_integral(true, "", &digits);
+    }
+}
+
+/// Sums of any size fit, so checked, saturating and wrapping sums are all the same
+impl Accumulator for BigUint {
+    fn zero() -> Self {
+        return BigUint::zero();
+    }
+
+    fn checked_accumulate(self, value: usize) -> Option<Self> {
+        return Some(self + BigUint::from(value));
+    }
+
+    fn saturating_accumulate(self, value: usize) -> Self {
+        return self + BigUint::from(value);
+    }
+
+    fn wrapping_accumulate(self, value: usize) -> Self {
+        return self + BigUint::from(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::Rng;
+
+    fn random_u64s(seed: u64) -> Vec<u64> {
+        let mut rng = Rng::new(seed);
+
+        return (0..200).map(|i| if i % 10 == 0 { u64::MAX - i } else { rng.below(u64::MAX) >> (i % 64) }).collect();
+    }
+
+    #[test]
+    fn test_from() {
+        assert!(BigUint::from(0u8).is_zero());
+        assert_eq!(BigUint::from(7u8), BigUint::from(7u128));
+        assert_eq!(BigUint::from(u128::MAX).limbs, [u32::MAX; 4]);
+        assert_eq!(BigUint::default(), BigUint::zero());
+    }
+
+    #[test]
+    fn test_cmp() {
+        let nums = random_u64s(1);
+
+        for pair in nums.windows(2) {
+            assert_eq!(BigUint::from(pair[0]).cmp(&BigUint::from(pair[1])), pair[0].cmp(&pair[1]));
+        }
+
+        assert!(BigUint::from(u64::MAX) < BigUint::from(u64::MAX as u128 + 1));
+    }
+
+    #[test]
+    fn test_add() {
+        let nums = random_u64s(2);
+
+        for pair in nums.windows(2) {
+            let expected = pair[0] as u128 + pair[1] as u128;
+
+            assert_eq!(BigUint::from(pair[0]) + BigUint::from(pair[1]), BigUint::from(expected));
+        }
+
+        assert_eq!((BigUint::from(u128::MAX) + BigUint::from(1u8)).limbs, [0, 0, 0, 0, 1]);
+        assert_eq!(BigUint::zero() + BigUint::zero(), BigUint::zero());
+    }
+
+    #[test]
+    fn test_mul() {
+        let nums = random_u64s(3);
+
+        for pair in nums.windows(2) {
+            let expected = pair[0] as u128 * pair[1] as u128;
+
+            assert_eq!(BigUint::from(pair[0]) * BigUint::from(pair[1]), BigUint::from(expected));
+        }
+
+        assert_eq!(BigUint::from(u128::MAX) * BigUint::zero(), BigUint::zero());
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        assert_eq!(
+            (BigUint::from(u128::MAX) * BigUint::from(u128::MAX)).limbs,
+            [1, 0, 0, 0, u32::MAX - 1, u32::MAX, u32::MAX, u32::MAX]
+        );
+    }
+
+    fn random_big(rng: &mut Rng, limbs: usize) -> BigUint {
+        return BigUint::from_limbs((0..limbs).map(|_| rng.next_u64() as u32).collect());
+    }
+
+    #[test]
+    fn test_karatsuba() {
+        let mut rng = Rng::new(7);
+
+        for (a_len, b_len) in [(32, 32), (33, 40), (64, 100), (200, 31), (300, 97), (257, 256)] {
+            let a = random_big(&mut rng, a_len);
+            let b = random_big(&mut rng, b_len);
+            let expected = BigUint::from_limbs(schoolbook_mul(&a.limbs, &b.limbs));
+
+            assert_eq!(&a * &b, expected, "{}x{}", a_len, b_len);
+            assert_eq!(&b * &a, expected, "{}x{}", b_len, a_len);
+        }
+
+        // (2^(32n) - 1)^2 = 2^(64n) - 2^(32n + 1) + 1, which carries through every limb
+        let max = BigUint::from_limbs(vec![u32::MAX; 100]);
+        let mut expected = vec![0; 200];
+
+        expected[0] = 1;
+        expected[100] = u32::MAX - 1;
+        expected[101..].fill(u32::MAX);
+
+        assert_eq!((&max * &max).limbs, expected);
+    }
+
+    #[test]
+    fn test_mul_small() {
+        for n in random_u64s(4) {
+            assert_eq!(BigUint::from(n).mul_small(u32::MAX), BigUint::from(n as u128 * u32::MAX as u128));
+        }
+
+        assert!(BigUint::from(5u8).mul_small(0).is_zero());
+    }
+
+    #[test]
+    fn test_div_rem_small() {
+        for n in random_u64s(5) {
+            let n = n as u128 * 3_000_000_019;
+
+            for divisor in [1, 7, 1_000_000_000, u32::MAX] {
+                let (quotient, remainder) = BigUint::from(n).div_rem_small(divisor);
+
+                assert_eq!(quotient, BigUint::from(n / divisor as u128));
+                assert_eq!(remainder as u128, n % divisor as u128);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_div_rem_small_by_zero() {
+        BigUint::from(1u8).div_rem_small(0);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(1_000_000_000u32).to_string(), "1000000000");
+        assert_eq!(BigUint::from(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(format!("{:>6}", BigUint::from(42u8)), "    42");
+
+        for n in random_u64s(6) {
+            assert_eq!(BigUint::from(n).to_string(), n.to_string());
+        }
+    }
+
+    #[test]
+    fn test_accumulate() {
+        let sum = (0..10).fold(BigUint::zero(), |sum, _| sum.checked_accumulate(usize::MAX).unwrap());
+
+        assert_eq!(sum, BigUint::from(usize::MAX as u128 * 10));
+    }
+}
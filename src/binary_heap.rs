@@ -0,0 +1,618 @@
+use std::mem;
+
+use crate::sort_trace::{trace, SortTrace, Tracer};
+
+/// Whether the smallest or the largest element of a [`BinaryHeap`] is on top
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HeapOrder {
+    Min,
+    Max,
+}
+
+impl HeapOrder {
+    /// Whether `a` belongs above `b` in the heap
+    fn before<T: PartialOrd>(self, a: &T, b: &T) -> bool {
+        return match self {
+            HeapOrder::Min => a < b,
+            HeapOrder::Max => a > b,
+        };
+    }
+}
+
+/// Identifies an element pushed onto a [`BinaryHeap`], see [`BinaryHeap::decrease_key`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Handle {
+    id: usize,
+    generation: usize,
+}
+
+struct Entry<T> {
+    value: T,
+    id: usize,
+}
+
+/// Position in the heap of the element with a given id
+///
+/// The id of a popped element goes back on the free list, and its generation is bumped so that
+/// handles to the popped element don't resolve to the next element given the id.
+struct Slot {
+    position: usize,
+    generation: usize,
+}
+
+/// Moves `arr[i]` up until its parent belongs above it, calling `on_swap` after every swap
+fn sift_up_by<T, F, S>(arr: &mut [T], mut i: usize, before: &F, on_swap: &mut S)
+where
+    F: Fn(&T, &T) -> bool,
+    S: FnMut(&[T], usize, usize),
+{
+    while i > 0 {
+        let parent = (i - 1) / 2;
+
+        if !before(&arr[i], &arr[parent]) {
+            return;
+        }
+
+        arr.swap(i, parent);
+        on_swap(arr, i, parent);
+        i = parent;
+    }
+}
+
+/// Moves `arr[i]` down until neither of its children belongs above it, calling `on_swap` after
+/// every swap
+fn sift_down_by<T, F, S>(arr: &mut [T], mut i: usize, before: &F, on_swap: &mut S)
+where
+    F: Fn(&T, &T) -> bool,
+    S: FnMut(&[T], usize, usize),
+{
+    loop {
+        let left = 2 * i + 1;
+        let right = left + 1;
+        let mut top = i;
+
+        if left < arr.len() && before(&arr[left], &arr[top]) {
+            top = left;
+        }
+
+        if right < arr.len() && before(&arr[right], &arr[top]) {
+            top = right;
+        }
+
+        if top == i {
+            return;
+        }
+
+        arr.swap(i, top);
+        on_swap(arr, i, top);
+        i = top;
+    }
+}
+
+/// Binary heap priority queue, with either the smallest or the largest element on top
+///
+/// [`push`](BinaryHeap::push) returns a [`Handle`] that can later be used to move the element
+/// closer to the top with [`decrease_key`](BinaryHeap::decrease_key), as needed by e.g.
+/// Dijkstra's algorithm. The ids behind the handles of popped elements are reused, so the heap
+/// takes memory proportional to the number of elements it holds, not to the number ever pushed.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::binary_heap::{BinaryHeap, HeapOrder};
+///
+/// let mut heap = BinaryHeap::new(HeapOrder::Min);
+///
+/// heap.push(5);
+/// let handle = heap.push(8);
+/// heap.push(3);
+///
+/// assert_eq!(heap.decrease_key(handle, 1), Some(8));
+/// assert_eq!(heap.pop(), Some(1));
+/// assert_eq!(heap.pop(), Some(3));
+/// assert_eq!(heap.decrease_key(handle, 0), None);
+/// ```
+pub struct BinaryHeap<T> {
+    entries: Vec<Entry<T>>,
+    /// Position of every element in `entries`, by id
+    slots: Vec<Slot>,
+    /// Ids of popped elements, free to be given to the next pushed ones
+    free: Vec<usize>,
+    order: HeapOrder,
+}
+
+impl<T: PartialOrd> BinaryHeap<T> {
+    pub fn new(order: HeapOrder) -> Self {
+        return Self {
+            entries: Vec::new(),
+            slots: Vec::new(),
+            free: Vec::new(),
+            order,
+        };
+    }
+
+    /// Builds a heap from `items` in O(n) time by sifting down every parent, starting from the
+    /// last one
+    pub fn heapify(items: Vec<T>, order: HeapOrder) -> Self {
+        let mut heap = Self {
+            slots: (0..items.len()).map(|position| Slot { position, generation: 0 }).collect(),
+            entries: items.into_iter().enumerate().map(|(id, value)| Entry { value, id }).collect(),
+            free: Vec::new(),
+            order,
+        };
+
+        for i in (0..heap.entries.len() / 2).rev() {
+            heap.sift_down(i);
+        }
+
+        return heap;
+    }
+
+    pub fn order(&self) -> HeapOrder {
+        return self.order;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    /// The element on top of the heap
+    pub fn peek(&self) -> Option<&T> {
+        return self.entries.first().map(|entry| &entry.value);
+    }
+
+    /// Position in `entries` of the element behind `handle`, or `None` if it has been popped
+    fn position(&self, handle: Handle) -> Option<usize> {
+        let slot = self.slots.get(handle.id)?;
+
+        if slot.generation != handle.generation {
+            return None;
+        }
+
+        return Some(slot.position);
+    }
+
+    /// The element behind `handle`, or `None` if it has been popped
+    pub fn get(&self, handle: Handle) -> Option<&T> {
+        return self.position(handle).map(|i| &self.entries[i].value);
+    }
+
+    pub fn push(&mut self, value: T) -> Handle {
+        let position = self.entries.len();
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.slots[id].position = position;
+                id
+            }
+            None => {
+                self.slots.push(Slot { position, generation: 0 });
+                self.slots.len() - 1
+            }
+        };
+
+        self.entries.push(Entry { value, id });
+        self.sift_up(position);
+
+        return Handle {
+            id,
+            generation: self.slots[id].generation,
+        };
+    }
+
+    /// Removes and returns the element on top of the heap
+    pub fn pop(&mut self) -> Option<T> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let last = self.entries.len() - 1;
+
+        self.entries.swap(0, last);
+
+        let entry = self.entries.pop().unwrap();
+
+        self.slots[entry.id].generation += 1;
+        self.free.push(entry.id);
+
+        if !self.entries.is_empty() {
+            self.slots[self.entries[0].id].position = 0;
+            self.sift_down(0);
+        }
+
+        return Some(entry.value);
+    }
+
+    /// Replaces the element behind `handle` with `value`, which must belong at least as close to
+    /// the top (smaller for a min-heap, larger for a max-heap)
+    ///
+    /// Returns the old value, or `None` if the element has already been popped.
+    ///
+    /// # Panics
+    /// When `value` belongs further from the top than the current value.
+    pub fn decrease_key(&mut self, handle: Handle, value: T) -> Option<T> {
+        let i = self.position(handle)?;
+
+        assert!(
+            !self.order.before(&self.entries[i].value, &value),
+            "new key belongs further from the top of the heap than the current one"
+        );
+
+        let old = mem::replace(&mut self.entries[i].value, value);
+
+        self.sift_up(i);
+
+        return Some(old);
+    }
+
+    /// All elements in ascending order, whichever [`HeapOrder`] the heap has
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let order = self.order;
+        let mut result = Vec::with_capacity(self.len());
+
+        while let Some(value) = self.pop() {
+            result.push(value);
+        }
+
+        if order == HeapOrder::Max {
+            result.reverse();
+        }
+
+        return result;
+    }
+
+    /// Number of ids the heap keeps a position for, popped ones included
+    #[cfg(test)]
+    pub(crate) fn slot_count(&self) -> usize {
+        return self.slots.len();
+    }
+
+    fn sift_up(&mut self, i: usize) {
+        let order = self.order;
+        let slots = &mut self.slots;
+
+        sift_up_by(
+            &mut self.entries,
+            i,
+            &|a: &Entry<T>, b: &Entry<T>| order.before(&a.value, &b.value),
+            &mut |entries: &[Entry<T>], a, b| {
+                slots[entries[a].id].position = a;
+                slots[entries[b].id].position = b;
+            },
+        );
+    }
+
+    fn sift_down(&mut self, i: usize) {
+        let order = self.order;
+        let slots = &mut self.slots;
+
+        sift_down_by(
+            &mut self.entries,
+            i,
+            &|a: &Entry<T>, b: &Entry<T>| order.before(&a.value, &b.value),
+            &mut |entries: &[Entry<T>], a, b| {
+                slots[entries[a].id].position = a;
+                slots[entries[b].id].position = b;
+            },
+        );
+    }
+}
+
+/// Binary heap without handles, for merges that never call [`BinaryHeap::decrease_key`]
+///
+/// Holds nothing but its elements, so it never needs memory for elements that have been popped.
+pub(crate) struct SimpleHeap<T> {
+    items: Vec<T>,
+    order: HeapOrder,
+}
+
+impl<T: PartialOrd> SimpleHeap<T> {
+    pub(crate) fn new(order: HeapOrder) -> Self {
+        return Self { items: Vec::new(), order };
+    }
+
+    pub(crate) fn push(&mut self, value: T) {
+        let order = self.order;
+        let last = self.items.len();
+
+        self.items.push(value);
+        sift_up_by(
+            &mut self.items,
+            last,
+            &|a: &T, b: &T| order.before(a, b),
+            &mut |_: &[T], _, _| {},
+        );
+    }
+
+    /// Removes and returns the element on top of the heap
+    pub(crate) fn pop(&mut self) -> Option<T> {
+        let order = self.order;
+        let last = self.items.len().checked_sub(1)?;
+
+        self.items.swap(0, last);
+
+        let top = self.items.pop();
+
+        sift_down_by(&mut self.items, 0, &|a: &T, b: &T| order.before(a, b), &mut |_: &[T], _, _| {});
+
+        return top;
+    }
+}
+
+/// In-place heap sort
+///
+/// Turns `arr` into a max-heap in O(n), then repeatedly swaps the top of the heap behind it and
+/// sifts the new top down. Takes O(n log n) time in the worst case and O(1) extra space. The
+/// sort is not stable.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([4, 1, 3, 9, 7]);
+///
+/// algorithms_exercises::binary_heap::heap_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([1, 3, 4, 7, 9]));
+/// ```
+pub fn heap_sort<T: PartialOrd>(arr: &mut [T]) {
+    let before = |a: &T, b: &T| a > b;
+
+    for i in (0..arr.len() / 2).rev() {
+        sift_down_by(arr, i, &before, &mut |_: &[T], _, _| {});
+    }
+
+    for end in (1..arr.len()).rev() {
+        arr.swap(0, end);
+        sift_down_by(&mut arr[..end], 0, &before, &mut |_: &[T], _, _| {});
+    }
+}
+
+/// [`sift_down_by`] of a max-heap in `arr[lo..hi]`, with `i` relative to `lo`, recording every
+/// comparison and swap at its index in `arr`
+fn sift_down_traced<T>(arr: &mut [T], lo: usize, hi: usize, mut i: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    loop {
+        let left = 2 * i + 1;
+        let right = left + 1;
+        let mut top = i;
+
+        for child in [left, right] {
+            if lo + child < hi {
+                tracer.compare(lo + child, lo + top);
+
+                if arr[lo + child] > arr[lo + top] {
+                    top = child;
+                }
+            }
+        }
+
+        if top == i {
+            return;
+        }
+
+        tracer.swap(arr, lo + i, lo + top);
+        i = top;
+    }
+}
+
+/// [`heap_sort`] of `arr[lo..hi]`, recording every comparison and swap at its index in `arr`
+pub(crate) fn heap_sort_traced<T>(arr: &mut [T], lo: usize, hi: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    for i in (0..(hi - lo) / 2).rev() {
+        sift_down_traced(arr, lo, hi, i, tracer);
+    }
+
+    for end in (lo + 1..hi).rev() {
+        tracer.swap(arr, lo, end);
+        sift_down_traced(arr, lo, end, 0, tracer);
+    }
+}
+
+/// Runs [`heap_sort`] on a copy of `arr` and lends `consume` its trace, recording every comparison
+/// and swap
+pub fn heap_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| heap_sort_traced(arr, 0, arr.len(), tracer));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{generate, Distribution};
+
+    fn assert_heap<T: PartialOrd>(heap: &BinaryHeap<T>) {
+        for (i, entry) in heap.entries.iter().enumerate() {
+            assert_eq!(heap.slots[entry.id].position, i);
+
+            if i > 0 {
+                assert!(!heap.order.before(&entry.value, &heap.entries[(i - 1) / 2].value));
+            }
+        }
+    }
+
+    #[test]
+    fn test_push_pop() {
+        let nums = generate(Distribution::Random, 200, 7);
+        let mut sorted = nums.clone();
+
+        sorted.sort();
+
+        for order in [HeapOrder::Min, HeapOrder::Max] {
+            let mut heap = BinaryHeap::new(order);
+
+            for &n in &nums {
+                heap.push(n);
+            }
+
+            assert_heap(&heap);
+            assert_eq!(heap.len(), 200);
+
+            let mut popped = Vec::new();
+
+            while let Some(n) = heap.pop() {
+                popped.push(n);
+            }
+
+            if order == HeapOrder::Max {
+                popped.reverse();
+            }
+
+            assert_eq!(popped, sorted);
+            assert!(heap.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_peek() {
+        let mut heap = BinaryHeap::new(HeapOrder::Max);
+
+        assert_eq!(heap.peek(), None);
+
+        heap.push(2);
+        heap.push(7);
+        heap.push(4);
+
+        assert_eq!(heap.peek(), Some(&7));
+        assert_eq!(heap.pop(), Some(7));
+        assert_eq!(heap.peek(), Some(&4));
+    }
+
+    #[test]
+    fn test_heapify() {
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 300, 8);
+            let mut sorted = nums.clone();
+
+            sorted.sort();
+
+            for order in [HeapOrder::Min, HeapOrder::Max] {
+                let heap = BinaryHeap::heapify(nums.clone(), order);
+
+                assert_heap(&heap);
+                assert_eq!(heap.into_sorted_vec(), sorted);
+            }
+        }
+
+        assert!(BinaryHeap::<u8>::heapify(Vec::new(), HeapOrder::Min).is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key() {
+        let mut heap = BinaryHeap::new(HeapOrder::Min);
+        let handles: Vec<Handle> = (10..20).map(|n| heap.push(n)).collect();
+
+        assert_eq!(heap.decrease_key(handles[9], 5), Some(19));
+        assert_eq!(heap.decrease_key(handles[4], 14), Some(14));
+        assert_heap(&heap);
+        assert_eq!(heap.get(handles[9]), Some(&5));
+        assert_eq!(heap.pop(), Some(5));
+        assert_eq!(heap.get(handles[9]), None);
+        assert_eq!(heap.decrease_key(handles[9], 0), None);
+
+        let mut max_heap = BinaryHeap::new(HeapOrder::Max);
+        let handle = max_heap.push(1);
+
+        max_heap.push(3);
+
+        assert_eq!(max_heap.decrease_key(handle, 4), Some(1));
+        assert_eq!(max_heap.into_sorted_vec(), [3, 4]);
+    }
+
+    #[test]
+    fn test_reused_handles() {
+        let mut heap = BinaryHeap::new(HeapOrder::Min);
+        let first = heap.push(1);
+
+        heap.push(2);
+
+        for n in 3..10_000 {
+            heap.push(n);
+            heap.pop();
+            assert_heap(&heap);
+        }
+
+        // Popping and pushing never held more than three elements at once
+        assert_eq!(heap.slot_count(), 3);
+        assert_eq!(heap.len(), 2);
+
+        // The id of the first element was given to a later one, its handle must not find it
+        assert_eq!(heap.get(first), None);
+        assert_eq!(heap.decrease_key(first, 0), None);
+
+        let handle = heap.push(20_000);
+
+        assert_eq!(heap.decrease_key(handle, 0), Some(20_000));
+        assert_eq!(heap.pop(), Some(0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_decrease_key_wrong_direction() {
+        let mut heap = BinaryHeap::new(HeapOrder::Min);
+        let handle = heap.push(3);
+
+        heap.decrease_key(handle, 4);
+    }
+
+    #[test]
+    fn test_simple_heap() {
+        let nums = generate(Distribution::Random, 200, 10);
+        let mut sorted = nums.clone();
+        let mut heap = SimpleHeap::new(HeapOrder::Min);
+
+        sorted.sort();
+
+        assert_eq!(heap.pop(), None);
+
+        for &n in &nums {
+            heap.push(n);
+        }
+
+        assert_eq!((0..200).map_while(|_| heap.pop()).collect::<Vec<_>>(), sorted);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_heap_sort() {
+        let mut empty: [u8; 0] = [];
+
+        heap_sort(&mut empty);
+
+        for distribution in Distribution::ALL {
+            let mut nums = generate(distribution, 1000, 9);
+            let mut sorted = nums.clone();
+
+            sorted.sort();
+            heap_sort(&mut nums);
+
+            assert_eq!(nums, sorted, "{}", distribution);
+        }
+    }
+
+    #[test]
+    fn test_heap_sort_steps() {
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 200, 9);
+            let (traced, events) = heap_sort_steps(&nums, collect_trace);
+            let mut sorted = nums.clone();
+            let mut replayed = nums.clone();
+
+            heap_sort(&mut sorted);
+
+            assert_eq!(traced, sorted, "{}", distribution);
+
+            replay(&mut replayed, events);
+            assert_eq!(replayed, sorted, "{}", distribution);
+        }
+
+        assert_eq!(heap_sort_steps::<usize, _, _>(&[], |trace| trace.count()), 0);
+    }
+}
@@ -1,35 +1,271 @@
-pub fn binary_search(arr: &[isize], num: isize) -> Option<usize> {
-    let mut min = 0;
-    let mut max = arr.len() - 1;
-    let mut idx;
+use std::ops::Range;
 
-    while min <= max {
-        idx = (min + max) / 2;
+/// Integer type whose ranges can be bisected by [`search_first_true`]
+pub trait IntegerDomain: Copy + Ord {
+    /// Average of `self` and `other` rounded towards negative infinity, without overflowing
+    fn floor_midpoint(self, other: Self) -> Self;
 
-        let item = arr[idx];
+    fn successor(self) -> Self;
+}
+
+macro_rules! impl_integer_domain {
+    ($($t:ty),*) => {
+        $(
+            impl IntegerDomain for $t {
+                fn floor_midpoint(self, other: Self) -> Self {
+                    // The shared bits plus half of the differing ones, `>>` is arithmetic for
+                    // signed types
+                    return (self & other) + ((self ^ other) >> 1);
+                }
+
+                fn successor(self) -> Self {
+                    return self + 1;
+                }
+            }
+        )*
+    };
+}
+
+impl_integer_domain!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Smallest value in `range` for which `pred` returns true, or `range.end` if there is none
+///
+/// `pred` must be monotone over `range`: false up to some point and true from there on. Useful
+/// for searching over answers rather than arrays, e.g. the smallest capacity that passes a
+/// check. Calls `pred` O(log n) times for a range of `n` values.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::binary_search::search_first_true;
+///
+/// let weights = [3, 2, 2, 4, 1, 4];
+///
+/// // Smallest truck capacity that ships every package, in order, within 3 trips
+/// let trips = |capacity: u32| {
+///     let mut trips = 1;
+///     let mut load = 0;
+///
+///     for &weight in &weights {
+///         if load + weight > capacity {
+///             trips += 1;
+///             load = 0;
+///         }
+///
+///         load += weight;
+///     }
+///
+///     return trips;
+/// };
+///
+/// assert_eq!(search_first_true(4..17, |capacity| trips(capacity) <= 3), 6);
+/// ```
+pub fn search_first_true<T, P>(range: Range<T>, mut pred: P) -> T
+where
+    T: IntegerDomain,
+    P: FnMut(T) -> bool,
+{
+    let mut min = range.start;
+    let mut max = range.end;
+
+    while min < max {
+        let mid = min.floor_midpoint(max);
+
+        if pred(mid) {
+            max = mid;
+        } else {
+            min = mid.successor();
+        }
+    }
+
+    return min;
+}
+
+/// Smallest value between `min` and `max` for which `pred` returns true, to within `epsilon`
+///
+/// Like [`search_first_true`] for a monotone `pred` over the reals. Bisects until the interval is
+/// at most `epsilon` wide, `max_iterations` bisections have been made or the floats run out of
+/// precision, then returns the upper end of the interval, for which `pred` holds unless it
+/// doesn't hold anywhere (then `max` is returned).
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::binary_search::search_first_true_f64;
+///
+/// let root = search_first_true_f64(0.0, 2.0, 1e-12, 100, |x| x * x >= 2.0);
+///
+/// assert!((root - 2f64.sqrt()).abs() < 1e-9);
+/// ```
+pub fn search_first_true_f64<P>(min: f64, max: f64, epsilon: f64, max_iterations: usize, mut pred: P) -> f64
+where
+    P: FnMut(f64) -> bool,
+{
+    let mut min = min;
+    let mut max = max;
 
-        if item == num {
-            return Some(idx);
-        } else if item > num {
-            max = idx - 1;
+    for _ in 0..max_iterations {
+        let mid = min + (max - min) / 2.0;
+
+        if max - min <= epsilon || mid <= min || mid >= max {
+            break;
+        }
+
+        if pred(mid) {
+            max = mid;
         } else {
-            min = idx + 1;
+            min = mid;
         }
     }
 
-    return None;
+    return max;
+}
+
+/// Index of the first element of `arr` for which `pred` returns false
+///
+/// `arr` must be partitioned by `pred`: every element it holds for comes before every element
+/// it doesn't. Returns `arr.len()` when `pred` holds for all of them.
+///
+/// # Example
+/// ```
+/// let nums = [1, 2, 3, 10, 11];
+///
+/// assert_eq!(algorithms_exercises::binary_search::partition_point(&nums, |&n| n < 5), 3);
+/// ```
+pub fn partition_point<T, P>(arr: &[T], mut pred: P) -> usize
+where
+    P: FnMut(&T) -> bool,
+{
+    return search_first_true(0..arr.len(), |idx| !pred(&arr[idx]));
+}
+
+/// Index of the first element of the sorted `arr` that is not less than `target`
+pub fn lower_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    return partition_point(arr, |item| item < target);
+}
+
+/// Index of the first element of the sorted `arr` that is greater than `target`
+pub fn upper_bound<T: Ord>(arr: &[T], target: &T) -> usize {
+    return partition_point(arr, |item| item <= target);
+}
+
+/// Range of the elements of the sorted `arr` that are equal to `target`, empty (at the insertion
+/// point) when there are none
+pub fn equal_range<T: Ord>(arr: &[T], target: &T) -> Range<usize> {
+    return lower_bound(arr, target)..upper_bound(arr, target);
+}
+
+/// Searches the sorted `arr` for `target`
+///
+/// Returns `Ok` with the index of the first element equal to `target`, or `Err` with the index
+/// where it could be inserted to keep `arr` sorted.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::binary_search::binary_search;
+///
+/// let nums = [1, 3, 3, 3, 8];
+///
+/// assert_eq!(binary_search(&nums, &3), Ok(1));
+/// assert_eq!(binary_search(&nums, &5), Err(4));
+/// assert_eq!(binary_search(&[], &5), Err(0));
+/// ```
+pub fn binary_search<T: Ord>(arr: &[T], target: &T) -> Result<usize, usize> {
+    let idx = lower_bound(arr, target);
+
+    if idx < arr.len() && arr[idx] == *target {
+        return Ok(idx);
+    }
+
+    return Err(idx);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_floor_midpoint() {
+        assert_eq!(3u8.floor_midpoint(8), 5);
+        assert_eq!(u64::MAX.floor_midpoint(u64::MAX - 2), u64::MAX - 1);
+        assert_eq!((-7i32).floor_midpoint(2), -3);
+        assert_eq!(i64::MIN.floor_midpoint(i64::MAX), -1);
+        assert_eq!((-1i8).floor_midpoint(-1), -1);
+    }
+
+    #[test]
+    fn test_search_first_true() {
+        assert_eq!(search_first_true(0..100, |n: u32| n * n >= 2000), 45);
+        assert_eq!(search_first_true(-50..50, |n: i32| n >= -7), -7);
+        assert_eq!(search_first_true(0..10, |_: usize| false), 10);
+        assert_eq!(search_first_true(0..10, |_: usize| true), 0);
+        assert_eq!(search_first_true(5..5, |_: usize| true), 5);
+        assert_eq!(search_first_true(i64::MIN..i64::MAX, |n| n >= i64::MAX - 1), i64::MAX - 1);
+        assert_eq!(search_first_true(0..u128::MAX, |n| n > 1 << 100), (1 << 100) + 1);
+    }
+
+    #[test]
+    fn test_search_first_true_f64() {
+        let mut calls = 0;
+        let cube_root = search_first_true_f64(0.0, 10.0, 1e-9, 1000, |x| {
+            calls += 1;
+
+            return x * x * x >= 30.0;
+        });
+
+        assert!((cube_root - 30f64.cbrt()).abs() < 1e-8);
+        assert!(calls <= 40);
+        assert_eq!(search_first_true_f64(0.0, 1.0, 0.0, 3, |_| true), 0.125);
+        assert_eq!(search_first_true_f64(0.0, 1.0, 1e-9, 100, |_| false), 1.0);
+        // Without an epsilon the bisection still stops once no float lies between the bounds
+        assert!(search_first_true_f64(1.0, 2.0, 0.0, usize::MAX, |x| x >= 1.5) == 1.5);
+    }
+
+    #[test]
+    fn test_partition_point() {
+        assert_eq!(partition_point(&[] as &[u8], |_| true), 0);
+        assert_eq!(partition_point(&[1, 2, 3], |&n| n < 10), 3);
+        assert_eq!(partition_point(&[1, 2, 3], |&n| n < 0), 0);
+        assert_eq!(partition_point(&[true, true, false], |&b| b), 2);
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        let nums = [1, 3, 3, 3, 8];
+
+        assert_eq!(lower_bound(&nums, &0), 0);
+        assert_eq!(lower_bound(&nums, &3), 1);
+        assert_eq!(lower_bound(&nums, &4), 4);
+        assert_eq!(lower_bound(&nums, &9), 5);
+        assert_eq!(lower_bound(&[], &1), 0);
+    }
+
+    #[test]
+    fn test_upper_bound() {
+        let nums = [1, 3, 3, 3, 8];
+
+        assert_eq!(upper_bound(&nums, &0), 0);
+        assert_eq!(upper_bound(&nums, &3), 4);
+        assert_eq!(upper_bound(&nums, &8), 5);
+        assert_eq!(upper_bound(&[], &1), 0);
+    }
+
+    #[test]
+    fn test_equal_range() {
+        let words = ["apple", "fig", "fig", "pear"];
+
+        assert_eq!(equal_range(&words, &"fig"), 1..3);
+        assert_eq!(equal_range(&words, &"kiwi"), 3..3);
+        assert_eq!(equal_range(&[], &"fig"), 0..0);
+    }
+
     #[test]
     fn test_binary_search() {
-        assert_eq!(binary_search(&Vec::from([1]), 1), Some(0));
-        assert_eq!(binary_search(&Vec::from([3, 2, 1]), 2), Some(1));
-        assert_eq!(binary_search(&Vec::from([1, 5, 7, 4, 2, 3, 6]), 6), Some(6));
-        assert_eq!(binary_search(&Vec::from([1, 3, 4]), 2), None);
-        assert_eq!(binary_search(&Vec::from([1, 3, 4, 19]), 555), None);
+        assert_eq!(binary_search(&Vec::from([1]), &1), Ok(0));
+        assert_eq!(binary_search(&Vec::from([1, 2, 3]), &2), Ok(1));
+        assert_eq!(binary_search(&Vec::from([1, 2, 3, 4, 5, 6, 7]), &6), Ok(5));
+        assert_eq!(binary_search(&Vec::from([1, 3, 4]), &2), Err(1));
+        assert_eq!(binary_search(&Vec::from([1, 3, 4, 19]), &555), Err(4));
+        assert_eq!(binary_search(&Vec::from([1, 3, 4]), &-5), Err(0));
+        assert_eq!(binary_search(&Vec::<isize>::new(), &1), Err(0));
+        assert_eq!(binary_search(&Vec::from([2, 2, 2, 2]), &2), Ok(0));
     }
 }
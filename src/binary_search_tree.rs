@@ -1,3 +1,6 @@
+// The tests compare `find` with literal booleans
+#![allow(clippy::bool_assert_comparison)]
+
 /// Binary search tree implementation
 ///
 /// # Example
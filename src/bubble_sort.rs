@@ -1,5 +1,10 @@
+use crate::sort_stats::SortStats;
+use crate::sort_trace::{trace, SortTrace};
+
 /// Bubble sort algorithm implementation
 ///
+/// Only strictly greater neighbours are swapped, so the sort is stable.
+///
 /// # Example
 /// ```
 /// let mut nums = Vec::from([1, 3, 2, 5, 4]);
@@ -7,7 +12,7 @@
 /// algorithms_exercises::bubble_sort::bubble_sort(&mut nums);
 /// assert_eq!(nums, Vec::from([1, 2, 3, 4, 5]));
 /// ```
-pub fn bubble_sort<T>(arr: &mut Vec<T>)
+pub fn bubble_sort<T>(arr: &mut [T])
 where
     T: PartialOrd
 {
@@ -35,9 +40,157 @@ where
     }
 }
 
+/// Bidirectional bubble sort, alternating passes that move the largest element to the end and
+/// the smallest to the front
+///
+/// Every pass shrinks the unsorted range to just before its last swap, so small elements near
+/// the end (the "turtles" that slow bubble sort down) take one pass instead of one per position.
+/// The sort is stable.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([2, 3, 4, 5, 1]);
+///
+/// algorithms_exercises::bubble_sort::cocktail_shaker_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([1, 2, 3, 4, 5]));
+/// ```
+pub fn cocktail_shaker_sort<T>(arr: &mut [T])
+where
+    T: PartialOrd,
+{
+    let mut start = 0;
+    let mut end = arr.len();
+
+    while start + 1 < end {
+        let mut last_swap = start;
+
+        for index in start..(end - 1) {
+            if arr[index] > arr[index + 1] {
+                arr.swap(index, index + 1);
+                last_swap = index;
+            }
+        }
+
+        end = last_swap + 1;
+
+        let mut first_swap = end;
+
+        for index in ((start + 1)..end).rev() {
+            if arr[index - 1] > arr[index] {
+                arr.swap(index - 1, index);
+                first_swap = index;
+            }
+        }
+
+        start = first_swap;
+    }
+}
+
+/// [`bubble_sort`] that records its comparisons and swaps in `stats`
+pub fn bubble_sort_with_stats<T>(arr: &mut [T], stats: &mut SortStats)
+where
+    T: PartialOrd
+{
+    if arr.is_empty() {
+        return;
+    }
+
+    let mut len = arr.len();
+
+    loop {
+        let mut has_swapped = false;
+
+        for index in 0..(len - 1) {
+            if stats.greater(&arr[index], &arr[index + 1]) {
+                stats.swap(arr, index, index + 1);
+                has_swapped = true;
+            }
+        }
+
+        if !has_swapped {
+            break;
+        }
+
+        len -= 1;
+    }
+}
+
+/// Runs [`bubble_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison and swap
+pub fn bubble_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut len = arr.len();
+
+        while len > 1 {
+            let mut has_swapped = false;
+
+            for index in 0..(len - 1) {
+                tracer.compare(index, index + 1);
+
+                if arr[index] > arr[index + 1] {
+                    tracer.swap(arr, index, index + 1);
+                    has_swapped = true;
+                }
+            }
+
+            if !has_swapped {
+                break;
+            }
+
+            len -= 1;
+        }
+    });
+}
+
+/// Runs [`cocktail_shaker_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison and swap
+pub fn cocktail_shaker_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut start = 0;
+        let mut end = arr.len();
+
+        while start + 1 < end {
+            let mut last_swap = start;
+
+            for index in start..(end - 1) {
+                tracer.compare(index, index + 1);
+
+                if arr[index] > arr[index + 1] {
+                    tracer.swap(arr, index, index + 1);
+                    last_swap = index;
+                }
+            }
+
+            end = last_swap + 1;
+
+            let mut first_swap = end;
+
+            for index in ((start + 1)..end).rev() {
+                tracer.compare(index - 1, index);
+
+                if arr[index - 1] > arr[index] {
+                    tracer.swap(arr, index - 1, index);
+                    first_swap = index;
+                }
+            }
+
+            start = first_swap;
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sort_trace::{collect_trace, SortEvent};
 
     #[test]
     fn test_bubble_sort() {
@@ -56,4 +209,75 @@ mod tests {
         assert_eq!(nums3, Vec::from([1, 2, 3, 4, 5]));
         assert_eq!(chars, Vec::from(['a', 'b', 'd', 'f', 'g']));
     }
+
+    #[test]
+    fn test_cocktail_shaker_sort() {
+        let mut nums1: Vec<usize> = Vec::new();
+        let mut nums2 = Vec::from([10, 5, 3, 8, 2, 6, 4, 7, 9, 1]);
+        let mut nums3 = Vec::from([5, 4, 3, 2, 1]);
+        let mut pairs = Vec::from([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+
+        cocktail_shaker_sort(&mut nums1);
+        cocktail_shaker_sort(&mut nums2);
+        cocktail_shaker_sort(&mut nums3);
+        cocktail_shaker_sort(&mut pairs);
+
+        assert_eq!(nums1, Vec::new());
+        assert_eq!(nums2, Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]));
+        assert_eq!(nums3, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!(pairs, Vec::from([(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]));
+    }
+
+    #[test]
+    fn test_bubble_sort_with_stats() {
+        let mut reversed = Vec::from([5, 4, 3, 2, 1]);
+        let mut sorted = Vec::from([1, 2, 3, 4, 5]);
+        let mut reversed_stats = SortStats::new();
+        let mut sorted_stats = SortStats::new();
+
+        bubble_sort_with_stats(&mut reversed, &mut reversed_stats);
+        bubble_sort_with_stats(&mut sorted, &mut sorted_stats);
+
+        assert_eq!(reversed, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!(sorted, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!((reversed_stats.comparisons, reversed_stats.swaps), (10, 10));
+        assert_eq!((sorted_stats.comparisons, sorted_stats.swaps), (4, 0));
+        assert_eq!((sorted_stats.moves, sorted_stats.max_depth, sorted_stats.bytes_allocated), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_bubble_sort_steps() {
+        let (traced, events) = bubble_sort_steps(&[3, 1, 2], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(0, 1),
+                SortEvent::Compare(1, 2),
+                SortEvent::Swap(1, 2),
+                SortEvent::Compare(0, 1),
+            ]
+        );
+        assert_eq!(bubble_sort_steps::<usize, _, _>(&[], |trace| trace.count()), 0);
+    }
+
+    #[test]
+    fn test_cocktail_shaker_sort_steps() {
+        let (traced, events) = cocktail_shaker_sort_steps(&[2, 3, 1], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Compare(1, 2),
+                SortEvent::Swap(1, 2),
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(0, 1),
+            ]
+        );
+        assert_eq!(cocktail_shaker_sort_steps::<usize, _, _>(&[], |trace| trace.count()), 0);
+    }
 }
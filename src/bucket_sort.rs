@@ -0,0 +1,204 @@
+use crate::insertion_sort::insertion_sort;
+use crate::sort_trace::{trace, SortTrace};
+
+/// Bucket sort for keys spread uniformly over their range
+///
+/// Spreads the `n` keys over `n` buckets of equal width between the smallest and the largest
+/// finite key, then insertion sorts every bucket. That takes O(n) expected time when the keys
+/// are uniformly distributed, and degrades to O(n²) when most of them land in the same bucket.
+/// Infinities go to the first or last bucket and NaNs are moved to the end. The sort is stable.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([0.42, 0.32, 0.23, 0.52, 0.25, 0.47, 0.51]);
+///
+/// algorithms_exercises::bucket_sort::bucket_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([0.23, 0.25, 0.32, 0.42, 0.47, 0.51, 0.52]));
+/// ```
+pub fn bucket_sort<T>(arr: &mut [T])
+where
+    T: Into<f64> + PartialOrd + Copy,
+{
+    let (buckets, nans) = distribute(arr);
+    let mut index = 0;
+
+    for mut bucket in buckets {
+        insertion_sort(&mut bucket);
+
+        for value in bucket {
+            arr[index] = value;
+            index += 1;
+        }
+    }
+
+    for value in nans {
+        arr[index] = value;
+        index += 1;
+    }
+}
+
+/// Spreads the keys of `arr` over `arr.len()` buckets in their original order, and returns the
+/// buckets and the NaNs
+fn distribute<T>(arr: &[T]) -> (Vec<Vec<T>>, Vec<T>)
+where
+    T: Into<f64> + Copy,
+{
+    let len = arr.len();
+    let finite = arr.iter().map(|&value| value.into()).filter(|value: &f64| value.is_finite());
+    let min = finite.clone().fold(f64::INFINITY, f64::min);
+    let max = finite.fold(f64::NEG_INFINITY, f64::max);
+    let mut buckets: Vec<Vec<T>> = (0..len).map(|_| Vec::new()).collect();
+    let mut nans = Vec::new();
+
+    for &value in arr.iter() {
+        let key: f64 = value.into();
+
+        if key.is_nan() {
+            nans.push(value);
+            continue;
+        }
+
+        // Float to int casts saturate and map NaN (from 0/0 when all keys are equal) to 0
+        let bucket = ((key - min) / (max - min) * len as f64) as usize;
+
+        buckets[bucket.min(len - 1)].push(value);
+    }
+
+    return (buckets, nans);
+}
+
+/// Runs [`bucket_sort`] on a copy of `arr` and lends `consume` its trace, recording every write,
+/// comparison and swap
+///
+/// The keys are first written back bucket by bucket, in their original order within each bucket
+/// and with the NaNs at the end, then every bucket is insertion sorted in place.
+pub fn bucket_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: Into<f64> + PartialOrd + Copy + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let (buckets, nans) = distribute(arr);
+        let mut ranges = Vec::new();
+        let mut index = 0;
+
+        for bucket in buckets {
+            ranges.push(index..index + bucket.len());
+
+            for value in bucket {
+                tracer.write(arr, index, value);
+                index += 1;
+            }
+        }
+
+        for value in nans {
+            tracer.write(arr, index, value);
+            index += 1;
+        }
+
+        for range in ranges {
+            for index in range.start + 1..range.end {
+                let mut j = index;
+
+                while j > range.start {
+                    tracer.compare(j - 1, j);
+
+                    if arr[j - 1] <= arr[j] {
+                        break;
+                    }
+
+                    tracer.swap(arr, j, j - 1);
+                    j -= 1;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{generate, Distribution};
+
+    #[test]
+    fn test_bucket_sort() {
+        let mut empty: Vec<f64> = Vec::new();
+        let mut equal = Vec::from([2.5f32; 10]);
+        let mut special = Vec::from([1.0, f64::NAN, f64::INFINITY, -3.0, f64::NEG_INFINITY, 0.5, f64::NAN]);
+        let mut ints = Vec::from([7u32, 1, 1000, 3, 3, 0]);
+
+        bucket_sort(&mut empty);
+        bucket_sort(&mut equal);
+        bucket_sort(&mut special);
+        bucket_sort(&mut ints);
+
+        assert!(empty.is_empty());
+        assert_eq!(equal, [2.5; 10]);
+        assert_eq!(special[..5], [f64::NEG_INFINITY, -3.0, 0.5, 1.0, f64::INFINITY]);
+        assert!(special[5..].iter().all(|value| value.is_nan()));
+        assert_eq!(ints, [0, 1, 3, 3, 7, 1000]);
+
+        for distribution in Distribution::ALL {
+            let mut nums: Vec<f64> = generate(distribution, 1000, 16).iter().map(|&n| n as f64).collect();
+            let mut expected = nums.clone();
+
+            expected.sort_by(f64::total_cmp);
+            bucket_sort(&mut nums);
+
+            assert_eq!(nums, expected, "{}", distribution);
+        }
+    }
+
+    #[test]
+    fn test_bucket_sort_stability() {
+        // Pairs are ordered and bucketed by their first element only
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        struct Keyed(f32, usize);
+
+        impl PartialOrd for Keyed {
+            fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+                return self.0.partial_cmp(&other.0);
+            }
+        }
+
+        impl From<Keyed> for f64 {
+            fn from(keyed: Keyed) -> f64 {
+                return keyed.0 as f64;
+            }
+        }
+
+        let mut pairs: Vec<Keyed> = (0..200).map(|i| Keyed((i * 7 % 10) as f32 / 10.0, i)).collect();
+
+        bucket_sort(&mut pairs);
+
+        assert!(pairs.windows(2).all(|w| w[0].0 < w[1].0 || (w[0].0 == w[1].0 && w[0].1 < w[1].1)));
+    }
+
+    #[test]
+    fn test_bucket_sort_steps() {
+        let special = Vec::from([1.0, f64::NAN, f64::INFINITY, -3.0, f64::NEG_INFINITY, 0.5, f64::NAN]);
+        let (traced, events) = bucket_sort_steps(&special, collect_trace);
+        let mut replayed = special.clone();
+
+        assert_eq!(traced[..5], [f64::NEG_INFINITY, -3.0, 0.5, 1.0, f64::INFINITY]);
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed[..5], [f64::NEG_INFINITY, -3.0, 0.5, 1.0, f64::INFINITY]);
+        assert!(replayed[5..].iter().all(|value| value.is_nan()));
+
+        for distribution in Distribution::ALL {
+            let nums: Vec<f64> = generate(distribution, 300, 17).iter().map(|&n| n as f64).collect();
+            let (traced, events) = bucket_sort_steps(&nums, collect_trace);
+            let mut sorted = nums.clone();
+            let mut replayed = nums.clone();
+
+            bucket_sort(&mut sorted);
+
+            assert_eq!(traced, sorted, "{}", distribution);
+
+            replay(&mut replayed, events);
+            assert_eq!(replayed, sorted, "{}", distribution);
+        }
+    }
+}
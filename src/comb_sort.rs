@@ -0,0 +1,113 @@
+use crate::sort_trace::{trace, SortTrace};
+
+/// Comb sort: bubble sort over elements `gap` apart, shrinking the gap by a factor of 1.3 after
+/// every pass until it reaches 1
+///
+/// Like Shell sort for insertion sort, the large gaps move small elements near the end (the
+/// "turtles" that make bubble sort slow) to the front in a few passes. Passes with a gap of 1
+/// repeat until nothing is swapped. The sort is not stable.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([8, 4, 1, 56, 3, -44, 23, -6, 28, 0]);
+///
+/// algorithms_exercises::comb_sort::comb_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([-44, -6, 0, 1, 3, 4, 8, 23, 28, 56]));
+/// ```
+pub fn comb_sort<T>(arr: &mut [T])
+where
+    T: PartialOrd,
+{
+    let mut gap = arr.len();
+    let mut sorted = false;
+
+    while !sorted {
+        gap = (gap * 10 / 13).max(1);
+        sorted = gap == 1;
+
+        for index in 0..arr.len().saturating_sub(gap) {
+            if arr[index] > arr[index + gap] {
+                arr.swap(index, index + gap);
+                sorted = false;
+            }
+        }
+    }
+}
+
+/// Runs [`comb_sort`] on a copy of `arr` and lends `consume` its trace, recording every comparison
+/// and swap
+pub fn comb_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut gap = arr.len();
+        let mut sorted = false;
+
+        while !sorted {
+            gap = (gap * 10 / 13).max(1);
+            sorted = gap == 1;
+
+            for index in 0..arr.len().saturating_sub(gap) {
+                tracer.compare(index, index + gap);
+
+                if arr[index] > arr[index + gap] {
+                    tracer.swap(arr, index, index + gap);
+                    sorted = false;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{generate, Distribution};
+
+    #[test]
+    fn test_comb_sort() {
+        let mut nums1: Vec<usize> = Vec::new();
+        let mut nums2 = Vec::from([10, 5, 3, 8, 2, 6, 4, 7, 9, 1]);
+        let mut chars = Vec::from(['g', 'd', 'a', 'b', 'f']);
+
+        comb_sort(&mut nums1);
+        comb_sort(&mut nums2);
+        comb_sort(&mut chars);
+
+        assert_eq!(nums1, Vec::new());
+        assert_eq!(nums2, Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]));
+        assert_eq!(chars, Vec::from(['a', 'b', 'd', 'f', 'g']));
+
+        for distribution in Distribution::ALL {
+            let mut nums = generate(distribution, 1000, 13);
+            let mut expected = nums.clone();
+
+            expected.sort();
+            comb_sort(&mut nums);
+
+            assert_eq!(nums, expected, "{}", distribution);
+        }
+    }
+
+    #[test]
+    fn test_comb_sort_steps() {
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 100, 13);
+            let (traced, events) = comb_sort_steps(&nums, collect_trace);
+            let mut sorted = nums.clone();
+            let mut replayed = nums.clone();
+
+            comb_sort(&mut sorted);
+
+            assert_eq!(traced, sorted, "{}", distribution);
+
+            replay(&mut replayed, events);
+            assert_eq!(replayed, sorted, "{}", distribution);
+        }
+
+        assert_eq!(comb_sort_steps::<usize, _, _>(&[], |trace| trace.count()), 0);
+    }
+}
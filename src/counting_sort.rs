@@ -0,0 +1,239 @@
+use crate::merge_sort::merge_sort;
+use crate::radix_sort::{apply_permutation, lsd_radix_sort, RadixKey};
+use crate::sort_trace::{trace, SortTrace};
+
+/// Stable counting sort of records by a small integer key
+///
+/// Counts how many records have each key in `0..=max_key`, turns the counts into the position
+/// where each key's records start and moves every record to its slot. Takes O(n + k) time and
+/// O(n + k) extra space for keys up to `k`, so it only pays off when `k` is small. Keys far larger
+/// than the number of records are ordered with a merge sort instead of counted. `key` is called
+/// once per record.
+///
+/// # Example
+/// ```
+/// let mut grades = Vec::from([("bob", 3), ("alice", 1), ("carol", 3), ("dave", 2)]);
+///
+/// algorithms_exercises::counting_sort::counting_sort_by_key(&mut grades, |grade| grade.1);
+/// assert_eq!(grades, Vec::from([("alice", 1), ("dave", 2), ("bob", 3), ("carol", 3)]));
+/// ```
+pub fn counting_sort_by_key<T, F>(arr: &mut [T], key: F)
+where
+    F: FnMut(&T) -> usize,
+{
+    let keys: Vec<usize> = arr.iter().map(key).collect();
+    let mut dest = destinations(&keys);
+
+    apply_permutation(arr, &mut dest);
+}
+
+/// Most counters the counting sorts allocate for `len` keys
+fn max_counters(len: usize) -> usize {
+    return len.saturating_mul(8).max(1 << 16);
+}
+
+/// Index every key moves to in a stable sort of `keys`
+fn destinations(keys: &[usize]) -> Vec<usize> {
+    let Some(&max_key) = keys.iter().max() else {
+        return Vec::new();
+    };
+    let Some(counters) = max_key.checked_add(2).filter(|&counters| counters <= max_counters(keys.len())) else {
+        let order = merge_sort(&keys.iter().copied().zip(0..).collect::<Vec<(usize, usize)>>());
+        let mut dest = vec![0; keys.len()];
+
+        for (position, (_, source)) in order.into_iter().enumerate() {
+            dest[source] = position;
+        }
+
+        return dest;
+    };
+    let mut starts = vec![0; counters];
+
+    for &key in keys {
+        starts[key + 1] += 1;
+    }
+
+    for key in 1..starts.len() {
+        starts[key] += starts[key - 1];
+    }
+
+    return keys
+        .iter()
+        .map(|&key| {
+            starts[key] += 1;
+
+            return starts[key] - 1;
+        })
+        .collect();
+}
+
+/// The key as an unsigned number with the same order, built from its radix bytes
+fn ordered_value<T: RadixKey>(value: &T) -> u128 {
+    return (0..T::BYTES).rev().fold(0, |acc, n| (acc << 8) | value.radix_byte(n) as u128);
+}
+
+/// Stable counting sort of integer keys whose values span a small range
+///
+/// Allocates one counter per value between the smallest and the largest key, so it only pays off
+/// when the keys are close together. Keys spanning a range much wider than their number, such as
+/// a handful of keys that are far apart or floats (whose bit patterns are far apart even for
+/// nearby values), are sorted with [`lsd_radix_sort`] instead.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([3i8, -2, 0, 3, -2, 1]);
+///
+/// algorithms_exercises::counting_sort::counting_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([-2, -2, 0, 1, 3, 3]));
+/// ```
+pub fn counting_sort<T: RadixKey>(arr: &mut [T]) {
+    let values: Vec<u128> = arr.iter().map(ordered_value).collect();
+    let Some(&min) = values.iter().min() else {
+        return;
+    };
+    let max = *values.iter().max().unwrap();
+
+    if usize::try_from(max - min).map_or(true, |range| range >= max_counters(arr.len())) {
+        lsd_radix_sort(arr);
+        return;
+    }
+
+    let keys: Vec<usize> = values.iter().map(|value| (value - min) as usize).collect();
+    let mut sorted = arr.to_vec();
+
+    for (&value, dest) in arr.iter().zip(destinations(&keys)) {
+        sorted[dest] = value;
+    }
+
+    arr.copy_from_slice(&sorted);
+}
+
+/// Runs [`counting_sort`] on a copy of `arr` and lends `consume` its trace, recording the writes
+/// that put every key in place
+pub fn counting_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: RadixKey + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut sorted = arr.to_vec();
+
+        counting_sort(&mut sorted);
+
+        for (i, value) in sorted.into_iter().enumerate() {
+            tracer.write(arr, i, value);
+        }
+    });
+}
+
+/// Runs [`counting_sort_by_key`] on a copy of `arr` and lends `consume` its trace, recording the
+/// swaps that move every record into place
+///
+/// The records are only moved once all keys are counted, so the trace only holds the swaps of the
+/// final permutation.
+pub fn counting_sort_by_key_steps<T, F, C, R>(arr: &[T], key: F, consume: C) -> R
+where
+    T: Clone + Send,
+    F: FnMut(&T) -> usize + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, move |arr, tracer| {
+        let keys: Vec<usize> = arr.iter().map(key).collect();
+        let mut dest = destinations(&keys);
+
+        for i in 0..arr.len() {
+            while dest[i] != i {
+                let d = dest[i];
+
+                tracer.swap(arr, i, d);
+                dest.swap(i, d);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort_trace::{collect_trace, replay, SortEvent};
+    use crate::test_support::{generate, is_stably_sorted, tag, Distribution};
+
+    #[test]
+    fn test_counting_sort_by_key() {
+        let mut words = Vec::from(["ccc", "a", "bb", "", "dd", "e"]);
+        let keys: Vec<u64> = generate(Distribution::Random, 1000, 14).iter().map(|key| key % 100).collect();
+        let mut tagged = tag(&keys);
+
+        counting_sort_by_key(&mut words, |word| word.len());
+        counting_sort_by_key(&mut tagged, |item| item.key as usize);
+        counting_sort_by_key(&mut Vec::<u8>::new(), |&n| n as usize);
+
+        assert_eq!(words, Vec::from(["", "a", "e", "bb", "dd", "ccc"]));
+        assert!(is_stably_sorted(&tagged));
+    }
+
+    #[test]
+    fn test_counting_sort() {
+        let mut signed = Vec::from([5i64, -3, 8, 0, 8, -100, 42, 7, 7, 1]);
+        let mut bytes: Vec<u8> = (0..=255).rev().collect();
+        let mut empty: Vec<u32> = Vec::new();
+        let mut tagged = tag(&generate(Distribution::FewUnique, 500, 15));
+
+        counting_sort(&mut signed);
+        counting_sort(&mut bytes);
+        counting_sort(&mut empty);
+        counting_sort(&mut tagged);
+
+        assert_eq!(signed, Vec::from([-100, -3, 0, 1, 5, 7, 7, 8, 8, 42]));
+        assert_eq!(bytes, (0..=255).collect::<Vec<u8>>());
+        assert!(empty.is_empty());
+        assert!(is_stably_sorted(&tagged));
+    }
+
+    #[test]
+    fn test_counting_sort_range_too_large() {
+        let mut huge = Vec::from([u128::MAX, 0]);
+        let mut far = Vec::from([1u64 << 40, 7, 0, 1 << 40]);
+        let mut records = Vec::from([(usize::MAX, 'a'), (0, 'b'), (usize::MAX, 'c'), (1 << 40, 'd')]);
+
+        counting_sort(&mut huge);
+        counting_sort(&mut far);
+        counting_sort_by_key(&mut records, |record| record.0);
+
+        assert_eq!(huge, [0, u128::MAX]);
+        assert_eq!(far, [0, 7, 1 << 40, 1 << 40]);
+        assert_eq!(records, [(0, 'b'), (1 << 40, 'd'), (usize::MAX, 'a'), (usize::MAX, 'c')]);
+        assert_eq!(counting_sort_steps(&[0u64, 1 << 40], |trace| trace.sorted().to_vec()), [0, 1 << 40]);
+    }
+
+    #[test]
+    fn test_counting_sort_steps() {
+        let (traced, events) = counting_sort_steps(&[3i8, -1, 3], collect_trace);
+
+        assert_eq!(traced, [-1, 3, 3]);
+        assert_eq!(
+            events,
+            [SortEvent::Write(0, -1), SortEvent::Write(1, 3), SortEvent::Write(2, 3)]
+        );
+
+        let tagged = tag(&generate(Distribution::FewUnique, 500, 15));
+        let mut replayed = tagged.clone();
+
+        counting_sort_steps(&tagged, |trace| replay(&mut replayed, trace));
+        assert!(is_stably_sorted(&replayed));
+    }
+
+    #[test]
+    fn test_counting_sort_by_key_steps() {
+        let (traced, events) = counting_sort_by_key_steps(&[(2, 'a'), (0, 'b'), (2, 'c')], |record| record.0, collect_trace);
+
+        assert_eq!(traced, [(0, 'b'), (2, 'a'), (2, 'c')]);
+        assert_eq!(events, [SortEvent::Swap(0, 1)]);
+
+        let tagged = tag(&generate(Distribution::FewUnique, 500, 16));
+        let mut replayed = tagged.clone();
+
+        counting_sort_by_key_steps(&tagged, |record| record.key as usize, |trace| replay(&mut replayed, trace));
+        assert!(is_stably_sorted(&replayed));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::binary_search::partition_point;
+
+/// Searches the sorted `arr` for `target` by galloping: probes indices 1, 2, 4, 8, … until it
+/// passes `target`, then binary searches the last stretch
+///
+/// Takes O(log i) comparisons to find an element at index `i`, so it beats
+/// [`binary_search`](crate::binary_search::binary_search) when the target is near the start of
+/// a long slice. Returns the same result: `Ok` with the index of the first equal element, or
+/// `Err` with the insertion point.
+///
+/// # Example
+/// ```
+/// let nums: Vec<u32> = (0..1_000_000).map(|n| n * 2).collect();
+///
+/// assert_eq!(algorithms_exercises::exponential_search::exponential_search(&nums, &20), Ok(10));
+/// assert_eq!(algorithms_exercises::exponential_search::exponential_search(&nums, &21), Err(11));
+/// ```
+pub fn exponential_search<T: Ord>(arr: &[T], target: &T) -> Result<usize, usize> {
+    let mut bound = 1;
+
+    while bound < arr.len() && arr[bound] < *target {
+        bound *= 2;
+    }
+
+    let min = bound / 2;
+    let max = bound.min(arr.len());
+    let idx = min + partition_point(&arr[min..max], |item| item < target);
+
+    if idx < arr.len() && arr[idx] == *target {
+        return Ok(idx);
+    }
+
+    return Err(idx);
+}
+
+/// First index for which `pred` returns false, over an unbounded sequence that `pred` holds for
+/// up to some point and not after
+///
+/// Gallops like [`exponential_search`] to find an upper bound, then bisects, so it needs
+/// O(log i) calls for an answer `i`. Useful for lazily generated or very large sequences whose
+/// length isn't known.
+///
+/// # Panics
+/// When `pred` holds for every index up to `usize::MAX / 2`.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::exponential_search::unbounded_partition_point;
+///
+/// // The first square above 1000 is 32²
+/// assert_eq!(unbounded_partition_point(|n| n * n <= 1000), 32);
+/// ```
+pub fn unbounded_partition_point<P>(mut pred: P) -> usize
+where
+    P: FnMut(usize) -> bool,
+{
+    if !pred(0) {
+        return 0;
+    }
+
+    let mut bound = 1;
+
+    while pred(bound) {
+        bound = bound.checked_mul(2).expect("predicate holds for every index");
+    }
+
+    // pred(bound / 2) holds and pred(bound) doesn't
+    let mut min = bound / 2 + 1;
+    let mut max = bound;
+
+    while min < max {
+        let idx = min + (max - min) / 2;
+
+        if pred(idx) {
+            min = idx + 1;
+        } else {
+            max = idx;
+        }
+    }
+
+    return min;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_search::binary_search;
+
+    #[test]
+    fn test_exponential_search() {
+        let nums: Vec<i32> = (0..100).map(|n| n / 3 * 3).collect();
+
+        for target in -2..110 {
+            assert_eq!(exponential_search(&nums, &target), binary_search(&nums, &target), "{}", target);
+        }
+
+        assert_eq!(exponential_search(&[], &1), Err(0));
+        assert_eq!(exponential_search(&[1], &1), Ok(0));
+        assert_eq!(exponential_search(&[1], &2), Err(1));
+    }
+
+    #[test]
+    fn test_unbounded_partition_point() {
+        let mut calls = 0;
+
+        assert_eq!(
+            unbounded_partition_point(|n| {
+                calls += 1;
+
+                return n < 1_000_000;
+            }),
+            1_000_000
+        );
+        assert!(calls <= 42);
+        assert_eq!(unbounded_partition_point(|_| false), 0);
+        assert_eq!(unbounded_partition_point(|n| n < 1), 1);
+        assert_eq!(unbounded_partition_point(|n| n < 2), 2);
+        assert_eq!(unbounded_partition_point(|n| n < 3), 3);
+    }
+}
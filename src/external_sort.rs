@@ -0,0 +1,480 @@
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::mem::size_of;
+use std::path::PathBuf;
+use std::process;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::binary_heap::{HeapOrder, SimpleHeap};
+use crate::merge_sort::merge_sort;
+
+static SORT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// Most runs merged at once, every one of them needs an open file and a read buffer
+const MAX_MERGE_RUNS: usize = 64;
+
+/// A value that can be written to and read back from a byte stream
+pub trait Record: Sized {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()>;
+
+    /// Reads the next record, returning `Ok(None)` at a clean end of stream
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Option<Self>>;
+
+    /// Approximate number of bytes the record occupies in memory
+    fn memory_size(&self) -> usize {
+        return size_of::<Self>();
+    }
+}
+
+/// Fills `buf` completely, returning `Ok(false)` if the stream ended before the first byte
+fn read_exact_or_eof<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<bool> {
+    let mut filled = 0;
+
+    while filled < buf.len() {
+        match reader.read(&mut buf[filled..]) {
+            Ok(0) if filled == 0 => return Ok(false),
+            Ok(0) => return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "truncated record")),
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    return Ok(true);
+}
+
+macro_rules! impl_record_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Record for $t {
+                fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+                    return writer.write_all(&self.to_le_bytes());
+                }
+
+                fn read_from<R: Read>(reader: &mut R) -> io::Result<Option<Self>> {
+                    let mut buf = [0; size_of::<$t>()];
+
+                    if !read_exact_or_eof(reader, &mut buf)? {
+                        return Ok(None);
+                    }
+
+                    return Ok(Some(<$t>::from_le_bytes(buf)));
+                }
+            }
+        )*
+    };
+}
+
+impl_record_for_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+impl Record for Vec<u8> {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&(self.len() as u64).to_le_bytes())?;
+        return writer.write_all(self);
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Option<Self>> {
+        let mut len = [0; 8];
+
+        if !read_exact_or_eof(reader, &mut len)? {
+            return Ok(None);
+        }
+
+        let mut bytes = vec![0; u64::from_le_bytes(len) as usize];
+
+        reader.read_exact(&mut bytes)?;
+
+        return Ok(Some(bytes));
+    }
+
+    fn memory_size(&self) -> usize {
+        return size_of::<Self>() + self.capacity();
+    }
+}
+
+impl Record for String {
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&(self.len() as u64).to_le_bytes())?;
+        return writer.write_all(self.as_bytes());
+    }
+
+    fn read_from<R: Read>(reader: &mut R) -> io::Result<Option<Self>> {
+        return match Vec::<u8>::read_from(reader)? {
+            Some(bytes) => String::from_utf8(bytes)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            None => Ok(None),
+        };
+    }
+
+    fn memory_size(&self) -> usize {
+        return size_of::<Self>() + self.capacity();
+    }
+}
+
+/// A sorted run on disk, removed when dropped
+struct RunFile {
+    path: PathBuf,
+}
+
+impl Drop for RunFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Sorts record streams that don't fit in memory
+///
+/// The input is split into runs of at most `memory_budget` bytes (as reported by
+/// [`Record::memory_size`]), each run is sorted with `merge_sort` and written to a temporary
+/// file, and the runs are then k-way merged into the output. When there are more than 64 runs,
+/// groups of 64 are first merged into longer runs, as many times as needed, so no more than 64
+/// files are open at once. Input that fits in the budget is sorted in memory without touching the
+/// disk. The sort is stable.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::external_sort::{ExternalSorter, Record};
+///
+/// let mut input = Vec::new();
+///
+/// for value in [5u32, 3, 9, 1] {
+///     value.write_to(&mut input).unwrap();
+/// }
+///
+/// let mut output = Vec::new();
+///
+/// ExternalSorter::new(8).sort::<u32, _, _>(input.as_slice(), &mut output).unwrap();
+///
+/// let mut reader = output.as_slice();
+///
+/// assert_eq!(u32::read_from(&mut reader).unwrap(), Some(1));
+/// ```
+pub struct ExternalSorter {
+    memory_budget: usize,
+    temp_dir: PathBuf,
+}
+
+impl ExternalSorter {
+    pub fn new(memory_budget: usize) -> Self {
+        return Self {
+            memory_budget,
+            temp_dir: env::temp_dir(),
+        };
+    }
+
+    /// Directory where the sorted runs are written, defaults to the system temp directory
+    pub fn with_temp_dir(mut self, temp_dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = temp_dir.into();
+
+        return self;
+    }
+
+    /// Sorts all records from `input` into `output`, returning the number of records written
+    pub fn sort<T, R, W>(&self, input: R, output: W) -> io::Result<usize>
+    where
+        T: Record + PartialOrd + Clone,
+        R: Read,
+        W: Write,
+    {
+        let sort_id = SORT_ID.fetch_add(1, Ordering::Relaxed);
+        let mut input = BufReader::new(input);
+        let mut output = BufWriter::new(output);
+        let mut runs = Vec::new();
+        let mut run = Vec::new();
+        let mut run_size = 0;
+        let mut count = 0;
+
+        while let Some(record) = T::read_from(&mut input)? {
+            run_size += record.memory_size();
+            run.push(record);
+            count += 1;
+
+            if run_size >= self.memory_budget {
+                runs.push(self.write_run(&run, sort_id, runs.len())?);
+                run.clear();
+                run_size = 0;
+            }
+        }
+
+        if runs.is_empty() {
+            for record in merge_sort(&run) {
+                record.write_to(&mut output)?;
+            }
+
+            output.flush()?;
+
+            return Ok(count);
+        }
+
+        if !run.is_empty() {
+            runs.push(self.write_run(&run, sort_id, runs.len())?);
+        }
+
+        drop(run);
+
+        let mut index = runs.len();
+
+        // Merging consecutive groups in order keeps equal records from earlier runs first
+        while runs.len() > MAX_MERGE_RUNS {
+            let mut merged = Vec::new();
+
+            for group in runs.chunks(MAX_MERGE_RUNS) {
+                let (run_file, mut writer) = self.create_run(sort_id, index)?;
+
+                self.merge_runs::<T, _>(group, &mut writer)?;
+                writer.flush()?;
+                merged.push(run_file);
+                index += 1;
+            }
+
+            runs = merged;
+        }
+
+        self.merge_runs::<T, _>(&runs, &mut output)?;
+        output.flush()?;
+
+        return Ok(count);
+    }
+
+    fn create_run(&self, sort_id: usize, index: usize) -> io::Result<(RunFile, BufWriter<File>)> {
+        let path = self
+            .temp_dir
+            .join(format!("external-sort-{}-{}-{}.run", process::id(), sort_id, index));
+        let file = OpenOptions::new().write(true).create_new(true).open(&path)?;
+
+        return Ok((RunFile { path }, BufWriter::new(file)));
+    }
+
+    fn write_run<T>(&self, run: &[T], sort_id: usize, index: usize) -> io::Result<RunFile>
+    where
+        T: Record + PartialOrd + Clone,
+    {
+        let (run_file, mut writer) = self.create_run(sort_id, index)?;
+
+        for record in merge_sort(run) {
+            record.write_to(&mut writer)?;
+        }
+
+        writer.flush()?;
+
+        return Ok(run_file);
+    }
+
+    fn merge_runs<T, W>(&self, runs: &[RunFile], output: &mut W) -> io::Result<()>
+    where
+        T: Record + PartialOrd,
+        W: Write,
+    {
+        let mut readers = Vec::with_capacity(runs.len());
+        // Ties between heads are broken by run index, which keeps the merge stable
+        let mut heads = SimpleHeap::new(HeapOrder::Min);
+
+        for (i, run) in runs.iter().enumerate() {
+            let mut reader = BufReader::new(File::open(&run.path)?);
+
+            if let Some(head) = T::read_from(&mut reader)? {
+                heads.push((head, i));
+            }
+
+            readers.push(reader);
+        }
+
+        while let Some((value, i)) = heads.pop() {
+            if let Some(next) = T::read_from(&mut readers[i])? {
+                heads.push((next, i));
+            }
+
+            value.write_to(output)?;
+        }
+
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{generate, is_stably_sorted, tag, Distribution, Tagged};
+    use std::path::Path;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("external-sort-test-{}-{}", process::id(), name));
+
+        fs::create_dir_all(&dir).unwrap();
+
+        return dir;
+    }
+
+    fn encode<T: Record>(values: &[T]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        for value in values {
+            value.write_to(&mut bytes).unwrap();
+        }
+
+        return bytes;
+    }
+
+    fn decode<T: Record>(bytes: &[u8]) -> Vec<T> {
+        let mut reader = bytes;
+        let mut result = Vec::new();
+
+        while let Some(value) = T::read_from(&mut reader).unwrap() {
+            result.push(value);
+        }
+
+        return result;
+    }
+
+    fn is_empty_dir(dir: &Path) -> bool {
+        return fs::read_dir(dir).unwrap().next().is_none();
+    }
+
+    #[test]
+    fn test_record_round_trip() {
+        assert_eq!(decode::<i64>(&encode(&[-1i64, 0, i64::MAX])), [-1, 0, i64::MAX]);
+        assert_eq!(
+            decode::<String>(&encode(&[String::from("b"), String::new(), String::from("ä")])),
+            ["b", "", "ä"]
+        );
+
+        let truncated = &encode(&[1u32])[..3];
+
+        assert_eq!(
+            u32::read_from(&mut &truncated[..]).unwrap_err().kind(),
+            io::ErrorKind::UnexpectedEof
+        );
+    }
+
+    #[test]
+    fn test_external_sort_in_memory() {
+        let dir = temp_dir("in-memory");
+        let input = encode(&[3u32, 1, 2]);
+        let mut output = Vec::new();
+        let sorter = ExternalSorter::new(1024).with_temp_dir(&dir);
+
+        assert_eq!(sorter.sort::<u32, _, _>(input.as_slice(), &mut output).unwrap(), 3);
+        assert_eq!(decode::<u32>(&output), [1, 2, 3]);
+        assert!(is_empty_dir(&dir));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_external_sort_runs() {
+        let dir = temp_dir("runs");
+        let values: Vec<u64> = (0..1000).map(|i| (i * 7919) % 1009).collect();
+        let input = encode(&values);
+        let mut output = Vec::new();
+        let sorter = ExternalSorter::new(64).with_temp_dir(&dir);
+        let mut expected = values.clone();
+
+        expected.sort();
+
+        assert_eq!(sorter.sort::<u64, _, _>(input.as_slice(), &mut output).unwrap(), 1000);
+        assert_eq!(decode::<u64>(&output), expected);
+        assert!(is_empty_dir(&dir));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_external_sort_strings() {
+        let dir = temp_dir("strings");
+        let words = ["pear", "apple", "fig", "banana", "cherry", "date", "apple", "kiwi"];
+        let values: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+        let input = encode(&values);
+        let mut output = Vec::new();
+        let sorter = ExternalSorter::new(100).with_temp_dir(&dir);
+
+        sorter.sort::<String, _, _>(input.as_slice(), &mut output).unwrap();
+
+        assert_eq!(
+            decode::<String>(&output),
+            ["apple", "apple", "banana", "cherry", "date", "fig", "kiwi", "pear"]
+        );
+        assert!(is_empty_dir(&dir));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    impl Record for Tagged {
+        fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+            self.key.write_to(writer)?;
+
+            return (self.tag as u64).write_to(writer);
+        }
+
+        fn read_from<R: Read>(reader: &mut R) -> io::Result<Option<Self>> {
+            let Some(key) = u64::read_from(reader)? else {
+                return Ok(None);
+            };
+            let tag = u64::read_from(reader)?.ok_or(io::ErrorKind::UnexpectedEof)?;
+
+            return Ok(Some(Tagged { key, tag: tag as usize }));
+        }
+    }
+
+    #[test]
+    fn test_external_sort_stability() {
+        let dir = temp_dir("stability");
+        let input = encode(&tag(&generate(Distribution::FewUnique, 500, 11)));
+        let mut output = Vec::new();
+        let sorter = ExternalSorter::new(256).with_temp_dir(&dir);
+
+        sorter.sort::<Tagged, _, _>(input.as_slice(), &mut output).unwrap();
+
+        assert_eq!(decode::<Tagged>(&output).len(), 500);
+        assert!(is_stably_sorted(&decode::<Tagged>(&output)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_external_sort_many_runs() {
+        // One record per run, far more runs than can be open at once
+        let dir = temp_dir("many-runs");
+        let values: Vec<u32> = (0..5000).map(|i| (i * 7919) % 5003).collect();
+        let mut output = Vec::new();
+        let sorter = ExternalSorter::new(4).with_temp_dir(&dir);
+        let mut expected = values.clone();
+
+        expected.sort();
+
+        assert_eq!(sorter.sort::<u32, _, _>(encode(&values).as_slice(), &mut output).unwrap(), 5000);
+        assert_eq!(decode::<u32>(&output), expected);
+        assert!(is_empty_dir(&dir));
+
+        let input = encode(&tag(&generate(Distribution::FewUnique, 500, 12)));
+
+        output.clear();
+        ExternalSorter::new(16).with_temp_dir(&dir).sort::<Tagged, _, _>(input.as_slice(), &mut output).unwrap();
+
+        assert!(is_stably_sorted(&decode::<Tagged>(&output)));
+        assert!(is_empty_dir(&dir));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_external_sort_errors() {
+        let dir = temp_dir("errors");
+        let mut input = encode(&[5u32, 4, 3, 2, 1]);
+        let mut output = Vec::new();
+        let sorter = ExternalSorter::new(8).with_temp_dir(&dir);
+
+        input.pop();
+
+        assert!(sorter.sort::<u32, _, _>(input.as_slice(), &mut output).is_err());
+        assert!(is_empty_dir(&dir));
+        assert!(sorter.sort::<u32, _, _>(&[][..], &mut output).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        let missing = ExternalSorter::new(8).with_temp_dir(dir.join("missing"));
+
+        assert!(missing.sort::<u32, _, _>(encode(&[2u32, 1, 0]).as_slice(), &mut output).is_err());
+    }
+}
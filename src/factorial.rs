@@ -1,3 +1,5 @@
+use crate::big_int::BigUint;
+
 pub fn factorial(n: usize) -> usize {
     if n <= 1 {
         return 1;
@@ -6,6 +8,34 @@ pub fn factorial(n: usize) -> usize {
     return n * factorial(n - 1);
 }
 
+/// Product of `lo..hi`, splitting the range in halves so that the numbers multiplied together
+/// stay about the same size
+fn range_product(lo: usize, hi: usize) -> BigUint {
+    if hi - lo <= 1 {
+        return BigUint::from(lo.max(1));
+    }
+
+    let mid = lo + (hi - lo) / 2;
+
+    return range_product(lo, mid) * range_product(mid, hi);
+}
+
+/// Exact `n!` for any `n`
+///
+/// Multiplying by 1, 2, … in turn would multiply an ever larger number by a small one `n` times.
+/// Multiplying the products of the two halves of `1..=n` instead keeps both sides of every
+/// multiplication balanced, so the large ones use `BigUint`'s Karatsuba multiplication.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::factorial::big_factorial;
+///
+/// assert_eq!(big_factorial(25).to_string(), "15511210043330985984000000");
+/// ```
+pub fn big_factorial(n: usize) -> BigUint {
+    return range_product(1, n + 1);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -16,4 +46,17 @@ mod tests {
         assert_eq!(factorial(5), 120);
         assert_eq!(factorial(13), 6227020800);
     }
+
+    #[test]
+    fn test_big_factorial() {
+        for n in 0..=20 {
+            assert_eq!(big_factorial(n), BigUint::from(factorial(n)));
+        }
+
+        let digits = big_factorial(1000).to_string();
+
+        assert_eq!(digits.len(), 2568);
+        assert!(digits.starts_with("402387260077"));
+        assert_eq!(digits.len() - digits.trim_end_matches('0').len(), 249);
+    }
 }
@@ -0,0 +1,100 @@
+use crate::sort_trace::{trace, SortTrace};
+
+/// Gnome sort: walks forward while neighbours are in order, and on finding a pair that isn't,
+/// swaps it and steps back
+///
+/// Does the same swaps as insertion sort but without a nested loop, which makes it O(n²) in
+/// general and O(n) on sorted input. Only strictly greater neighbours are swapped, so the sort
+/// is stable.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([3, 1, 2]);
+///
+/// algorithms_exercises::gnome_sort::gnome_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([1, 2, 3]));
+/// ```
+pub fn gnome_sort<T>(arr: &mut [T])
+where
+    T: PartialOrd,
+{
+    let mut index = 1;
+
+    while index < arr.len() {
+        if index == 0 || arr[index - 1] <= arr[index] {
+            index += 1;
+        } else {
+            arr.swap(index - 1, index);
+            index -= 1;
+        }
+    }
+}
+
+/// Runs [`gnome_sort`] on a copy of `arr` and lends `consume` its trace, recording every comparison
+/// and swap
+pub fn gnome_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut index = 1;
+
+        while index < arr.len() {
+            if index > 0 {
+                tracer.compare(index - 1, index);
+            }
+
+            if index == 0 || arr[index - 1] <= arr[index] {
+                index += 1;
+            } else {
+                tracer.swap(arr, index - 1, index);
+                index -= 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort_trace::{collect_trace, SortEvent};
+
+    #[test]
+    fn test_gnome_sort() {
+        let mut nums1: Vec<usize> = Vec::new();
+        let mut nums2 = Vec::from([10, 5, 3, 8, 2, 6, 4, 7, 9, 1]);
+        let mut nums3 = Vec::from([5, 4, 3, 2, 1]);
+        let mut chars = Vec::from(['g', 'd', 'a', 'b', 'f']);
+
+        gnome_sort(&mut nums1);
+        gnome_sort(&mut nums2);
+        gnome_sort(&mut nums3);
+        gnome_sort(&mut chars);
+
+        assert_eq!(nums1, Vec::new());
+        assert_eq!(nums2, Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]));
+        assert_eq!(nums3, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!(chars, Vec::from(['a', 'b', 'd', 'f', 'g']));
+    }
+
+    #[test]
+    fn test_gnome_sort_steps() {
+        let (traced, events) = gnome_sort_steps(&[2, 3, 1], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Compare(1, 2),
+                SortEvent::Swap(1, 2),
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(0, 1),
+                SortEvent::Compare(0, 1),
+                SortEvent::Compare(1, 2),
+            ]
+        );
+        assert_eq!(gnome_sort_steps::<usize, _, _>(&[], |trace| trace.count()), 0);
+    }
+}
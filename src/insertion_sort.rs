@@ -1,4 +1,12 @@
-pub fn insertion_sort<T>(arr: &mut Vec<T>)
+use std::cmp::Ordering;
+
+use crate::sort_stats::SortStats;
+use crate::sort_trace::{trace, SortTrace};
+
+/// In-place insertion sort
+///
+/// Elements only move past strictly greater ones, so the sort is stable.
+pub fn insertion_sort<T>(arr: &mut [T])
 where
     T: PartialOrd,
 {
@@ -12,9 +20,153 @@ where
     }
 }
 
+/// Insertion sort ordered by a comparator, like `slice::sort_by`
+///
+/// The sort is stable.
+pub fn insertion_sort_by<T, F>(arr: &mut [T], mut compare: F)
+where
+    F: FnMut(&T, &T) -> Ordering,
+{
+    for index in 1..arr.len() {
+        let mut j = index;
+
+        while j > 0 && compare(&arr[j - 1], &arr[j]) == Ordering::Greater {
+            arr.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Insertion sort that finds where each element goes with a binary search
+///
+/// Needs O(n log n) comparisons instead of O(n²), which pays off when comparisons are expensive,
+/// but still moves O(n²) elements. Elements are inserted after the equal ones already placed, so
+/// the sort is stable.
+///
+/// # Example
+/// ```
+/// let mut words = Vec::from(["pear", "fig", "apple"]);
+///
+/// algorithms_exercises::insertion_sort::binary_insertion_sort(&mut words);
+/// assert_eq!(words, Vec::from(["apple", "fig", "pear"]));
+/// ```
+pub fn binary_insertion_sort<T>(arr: &mut [T])
+where
+    T: PartialOrd,
+{
+    for index in 1..arr.len() {
+        let (sorted, rest) = arr.split_at(index);
+        let position = sorted.partition_point(|item| *item <= rest[0]);
+
+        arr[position..=index].rotate_right(1);
+    }
+}
+
+/// [`insertion_sort`] that records its comparisons and swaps in `stats`
+pub fn insertion_sort_with_stats<T>(arr: &mut [T], stats: &mut SortStats)
+where
+    T: PartialOrd,
+{
+    for index in 1..arr.len() {
+        let mut j = index;
+
+        while j > 0 && stats.greater(&arr[j - 1], &arr[j]) {
+            stats.swap(arr, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Runs [`insertion_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison and swap
+pub fn insertion_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        for index in 1..arr.len() {
+            let mut j = index;
+
+            while j > 0 {
+                tracer.compare(j - 1, j);
+
+                if arr[j - 1] <= arr[j] {
+                    break;
+                }
+
+                tracer.swap(arr, j, j - 1);
+                j -= 1;
+            }
+        }
+    });
+}
+
+/// Runs [`insertion_sort_by`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison and swap
+pub fn insertion_sort_by_steps<T, F, C, R>(arr: &[T], mut compare: F, consume: C) -> R
+where
+    T: Clone + Send,
+    F: FnMut(&T, &T) -> Ordering + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, move |arr, tracer| {
+        for index in 1..arr.len() {
+            let mut j = index;
+
+            while j > 0 {
+                tracer.compare(j - 1, j);
+
+                if compare(&arr[j - 1], &arr[j]) != Ordering::Greater {
+                    break;
+                }
+
+                tracer.swap(arr, j, j - 1);
+                j -= 1;
+            }
+        }
+    });
+}
+
+/// Runs [`binary_insertion_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison and swap
+///
+/// Each binary search compares the element being inserted with the middle of the sorted prefix,
+/// then the element is moved down to its place by swapping it with its left neighbour.
+pub fn binary_insertion_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        for index in 1..arr.len() {
+            let mut lo = 0;
+            let mut hi = index;
+
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+
+                tracer.compare(mid, index);
+
+                if arr[mid] <= arr[index] {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+
+            for j in (lo..index).rev() {
+                tracer.swap(arr, j, j + 1);
+            }
+        }
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sort_trace::{collect_trace, SortEvent};
+    use crate::test_support::{generate, is_stably_sorted, tag, Distribution};
 
     #[test]
     fn test_insertion_sort() {
@@ -33,4 +185,109 @@ mod tests {
         assert_eq!(nums3, Vec::from([1, 2, 3, 4, 5]));
         assert_eq!(chars, Vec::from(['a', 'b', 'd', 'f', 'g']));
     }
+
+    #[test]
+    fn test_insertion_sort_by() {
+        let mut nums = Vec::from([10, 5, 3, 8, 2, 6, 4, 7, 9, 1]);
+        let mut pairs = Vec::from([(2, 'a'), (1, 'b'), (2, 'c'), (1, 'd')]);
+
+        insertion_sort_by(&mut nums, |a, b| b.cmp(a));
+        insertion_sort_by(&mut pairs, |a, b| a.0.cmp(&b.0));
+
+        assert_eq!(nums, Vec::from([10, 9, 8, 7, 6, 5, 4, 3, 2, 1]));
+        assert_eq!(pairs, Vec::from([(1, 'b'), (1, 'd'), (2, 'a'), (2, 'c')]));
+    }
+
+    #[test]
+    fn test_binary_insertion_sort() {
+        let mut nums1: Vec<usize> = Vec::new();
+        let mut nums2 = Vec::from([10, 5, 3, 8, 2, 6, 4, 7, 9, 1]);
+        let mut nums3 = Vec::from([5, 4, 3, 2, 1]);
+        let mut chars = Vec::from(['g', 'd', 'a', 'b', 'f']);
+
+        binary_insertion_sort(&mut nums1);
+        binary_insertion_sort(&mut nums2);
+        binary_insertion_sort(&mut nums3);
+        binary_insertion_sort(&mut chars);
+
+        assert_eq!(nums1, Vec::new());
+        assert_eq!(nums2, Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]));
+        assert_eq!(nums3, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!(chars, Vec::from(['a', 'b', 'd', 'f', 'g']));
+    }
+
+    #[test]
+    fn test_insertion_sort_with_stats() {
+        let mut reversed = Vec::from([5, 4, 3, 2, 1]);
+        let mut sorted = Vec::from([1, 2, 3, 4, 5]);
+        let mut reversed_stats = SortStats::new();
+        let mut sorted_stats = SortStats::new();
+
+        insertion_sort_with_stats(&mut reversed, &mut reversed_stats);
+        insertion_sort_with_stats(&mut sorted, &mut sorted_stats);
+
+        assert_eq!(reversed, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!(sorted, Vec::from([1, 2, 3, 4, 5]));
+        assert_eq!((reversed_stats.comparisons, reversed_stats.swaps), (10, 10));
+        assert_eq!((sorted_stats.comparisons, sorted_stats.swaps), (4, 0));
+    }
+
+    #[test]
+    fn test_insertion_sort_steps() {
+        let (traced, events) = insertion_sort_steps(&[3, 1, 2], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(1, 0),
+                SortEvent::Compare(1, 2),
+                SortEvent::Swap(2, 1),
+                SortEvent::Compare(0, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_insertion_sort_by_steps() {
+        let (traced, events) = insertion_sort_by_steps(&[1, 3, 2], |a, b| b.cmp(a), collect_trace);
+
+        assert_eq!(traced, [3, 2, 1]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(1, 0),
+                SortEvent::Compare(1, 2),
+                SortEvent::Swap(2, 1),
+                SortEvent::Compare(0, 1),
+            ]
+        );
+
+        let keys = generate(Distribution::FewUnique, 200, 4);
+
+        insertion_sort_by_steps(&tag(&keys), |a, b| a.key.cmp(&b.key), |trace| assert!(is_stably_sorted(trace.sorted())));
+    }
+
+    #[test]
+    fn test_binary_insertion_sort_steps() {
+        let (traced, events) = binary_insertion_sort_steps(&[2, 3, 1], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Compare(1, 2),
+                SortEvent::Compare(0, 2),
+                SortEvent::Swap(1, 2),
+                SortEvent::Swap(0, 1),
+            ]
+        );
+
+        let keys = generate(Distribution::FewUnique, 200, 3);
+
+        assert!(binary_insertion_sort_steps(&tag(&keys), |trace| is_stably_sorted(trace.sorted())));
+    }
 }
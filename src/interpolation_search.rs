@@ -0,0 +1,139 @@
+use crate::binary_search::partition_point;
+
+/// Numeric key whose position in a sorted slice can be estimated from its value
+///
+/// Implemented for the integers and for `f32` and `f64`. Floats are only partially ordered, so a
+/// slice of them must not contain NaN to be sorted.
+pub trait NumericKey: PartialOrd + Copy {
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_numeric_key {
+    ($($t:ty),*) => {
+        $(
+            impl NumericKey for $t {
+                fn to_f64(self) -> f64 {
+                    return self as f64;
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_key!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+/// Searches the sorted `arr` for `target` by guessing its position from its value, assuming the
+/// values are spread evenly between the first and the last element
+///
+/// Takes O(log log n) probes on uniformly distributed keys. Skewed keys can make every guess
+/// land next to the previous one, so after `log2(n)` guesses the search finishes with a binary
+/// search and the worst case stays O(log n). Returns the same result as
+/// [`binary_search`](crate::binary_search::binary_search).
+///
+/// Float keys may include infinities, guesses that can't be computed from them fall back to the
+/// start of the range. A NaN `target` is never found.
+///
+/// # Example
+/// ```
+/// let nums: Vec<u64> = (0..1000).map(|n| n * 10).collect();
+///
+/// assert_eq!(algorithms_exercises::interpolation_search::interpolation_search(&nums, &420), Ok(42));
+/// assert_eq!(algorithms_exercises::interpolation_search::interpolation_search(&nums, &425), Err(43));
+/// ```
+pub fn interpolation_search<T: NumericKey>(arr: &[T], target: &T) -> Result<usize, usize> {
+    let mut min = 0;
+    let mut max = arr.len();
+    let mut guesses = usize::BITS - arr.len().leading_zeros();
+
+    // arr[..min] is less than target and arr[max..] is not
+    while min < max && guesses > 0 {
+        let (low, high) = (arr[min], arr[max - 1]);
+
+        if *target <= low {
+            max = min;
+            break;
+        }
+
+        if *target > high {
+            min = max;
+            break;
+        }
+
+        let fraction = (target.to_f64() - low.to_f64()) / (high.to_f64() - low.to_f64());
+        let idx = (min + (fraction * (max - 1 - min) as f64) as usize).min(max - 1);
+
+        if arr[idx] < *target {
+            min = idx + 1;
+        } else {
+            max = idx;
+        }
+
+        guesses -= 1;
+    }
+
+    let idx = min + partition_point(&arr[min..max], |item| item < target);
+
+    if idx < arr.len() && arr[idx] == *target {
+        return Ok(idx);
+    }
+
+    return Err(idx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_search::binary_search;
+
+    #[test]
+    fn test_interpolation_search() {
+        let uniform: Vec<i64> = (-50..50).map(|n| n * 4).collect();
+        let skewed: Vec<u64> = (0..64).map(|n| 1 << n).collect();
+        let duplicates = [1, 1, 1, 5, 5, 9, 9, 9, 9];
+
+        for target in -210..210 {
+            assert_eq!(interpolation_search(&uniform, &target), binary_search(&uniform, &target));
+        }
+
+        for target in skewed.iter().flat_map(|&n| [n - 1, n, n + 1]) {
+            assert_eq!(interpolation_search(&skewed, &target), binary_search(&skewed, &target));
+        }
+
+        for target in 0..11 {
+            assert_eq!(interpolation_search(&duplicates, &target), binary_search(&duplicates, &target));
+        }
+
+        assert_eq!(interpolation_search(&[u128::MAX], &u128::MAX), Ok(0));
+        assert_eq!(interpolation_search::<u8>(&[], &1), Err(0));
+    }
+
+    #[test]
+    fn test_interpolation_search_floats() {
+        let uniform: Vec<f64> = (0..200).map(|n| n as f64 * 0.5 - 20.0).collect();
+        let skewed: Vec<f32> = (0..40).map(|n| 1.5f32.powi(n)).collect();
+        let infinite = [f64::NEG_INFINITY, -1.0, 0.0, 2.5, 2.5, f64::INFINITY];
+        let position = |arr: &[f64], target: f64| {
+            let idx = arr.partition_point(|&item| item < target);
+
+            return if arr.get(idx) == Some(&target) { Ok(idx) } else { Err(idx) };
+        };
+
+        for target in (-100..200).map(|n| n as f64 * 0.25 - 25.0) {
+            assert_eq!(interpolation_search(&uniform, &target), position(&uniform, target), "{}", target);
+        }
+
+        for &n in &skewed {
+            let idx = interpolation_search(&skewed, &n).unwrap();
+
+            assert_eq!(skewed[idx], n);
+            assert_eq!(interpolation_search(&skewed, &(n * 1.1)), Err(idx + 1));
+        }
+
+        for target in [f64::NEG_INFINITY, -5.0, -1.0, 1.0, 2.5, 3.0, f64::INFINITY] {
+            assert_eq!(interpolation_search(&infinite, &target), position(&infinite, target), "{}", target);
+        }
+
+        assert!(interpolation_search(&uniform, &f64::NAN).is_err());
+        assert_eq!(interpolation_search(&[-0.0], &0.0), Ok(0));
+    }
+}
@@ -1,12 +1,34 @@
+#![allow(clippy::needless_return)]
+
 pub mod bubble_sort;
 pub mod insertion_sort;
+pub mod shell_sort;
+pub mod comb_sort;
+pub mod gnome_sort;
 pub mod merge_sort;
 pub mod quick_sort;
 pub mod radix_sort;
+pub mod counting_sort;
+pub mod bucket_sort;
+pub mod external_sort;
+pub mod parallel_sort;
+pub mod selection;
+pub mod sort_stats;
+pub mod sort_trace;
+pub mod sorter;
+pub mod test_support;
 pub mod nested_add;
 pub mod factorial;
+pub mod big_int;
 pub mod binary_search;
+pub mod exponential_search;
+pub mod interpolation_search;
+pub mod ternary_search;
+pub mod rotated_search;
+pub mod matrix_search;
+pub mod static_search_index;
 pub mod array_list;
 pub mod linked_list;
 pub mod binary_search_tree;
 pub mod avl_tree;
+pub mod binary_heap;
@@ -1,3 +1,6 @@
+// `LinkedList::new` takes no arguments but the type has no `Default`
+#![allow(clippy::new_without_default)]
+
 #[derive(Debug)]
 struct Node<T> {
     value: T,
@@ -0,0 +1,124 @@
+use crate::binary_search::search_first_true;
+
+/// Searches a matrix whose rows and columns are both sorted in ascending order for `target`
+///
+/// Walks a staircase along the boundary between the elements less than `target` and the rest,
+/// moving left in the columns and down the rows, so it takes O(rows + columns) comparisons.
+/// Returns `Ok((row, column))` for the first row containing `target`, or `Err` with the number
+/// of elements less than it. All rows must have the same length.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::matrix_search::staircase_search;
+///
+/// let matrix = [[1, 4, 7], [2, 5, 8], [3, 6, 9]];
+///
+/// assert_eq!(staircase_search(&matrix, &6), Ok((2, 1)));
+/// assert_eq!(staircase_search(&matrix, &0), Err(0));
+/// ```
+pub fn staircase_search<T, R>(matrix: &[R], target: &T) -> Result<(usize, usize), usize>
+where
+    T: Ord,
+    R: AsRef<[T]>,
+{
+    let mut col = matrix.first().map_or(0, |row| row.as_ref().len());
+    let mut less = 0;
+
+    for (r, row) in matrix.iter().enumerate() {
+        let row = row.as_ref();
+
+        // Columns are sorted, so the boundary only ever moves left going down
+        while col > 0 && row[col - 1] >= *target {
+            col -= 1;
+        }
+
+        if col < row.len() && row[col] == *target {
+            return Ok((r, col));
+        }
+
+        less += col;
+    }
+
+    return Err(less);
+}
+
+/// Searches a matrix that is sorted in row-major order, each row continuing where the one above
+/// ended, for `target`
+///
+/// Binary searches the matrix as if it were one flat sorted slice, in O(log(rows · columns))
+/// comparisons. Returns `Ok((row, column))` for the first element equal to `target`, or `Err`
+/// with its insertion point in the flattened matrix. All rows must have the same length.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::matrix_search::sorted_matrix_search;
+///
+/// let matrix = [[1, 3, 5], [7, 9, 11], [13, 15, 17]];
+///
+/// assert_eq!(sorted_matrix_search(&matrix, &9), Ok((1, 1)));
+/// assert_eq!(sorted_matrix_search(&matrix, &12), Err(6));
+/// ```
+pub fn sorted_matrix_search<T, R>(matrix: &[R], target: &T) -> Result<(usize, usize), usize>
+where
+    T: Ord,
+    R: AsRef<[T]>,
+{
+    let cols = matrix.first().map_or(0, |row| row.as_ref().len());
+    let len = matrix.len() * cols;
+    let at = |idx: usize| &matrix[idx / cols].as_ref()[idx % cols];
+    let idx = search_first_true(0..len, |idx| at(idx) >= target);
+
+    if idx < len && at(idx) == target {
+        return Ok((idx / cols, idx % cols));
+    }
+
+    return Err(idx);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_staircase_search() {
+        let matrix = Vec::from([
+            Vec::from([1, 4, 7, 11, 15]),
+            Vec::from([2, 5, 8, 12, 19]),
+            Vec::from([3, 6, 9, 16, 22]),
+            Vec::from([10, 13, 14, 17, 24]),
+            Vec::from([18, 21, 23, 26, 30]),
+        ]);
+
+        for target in 0..32 {
+            let less = matrix.iter().flatten().filter(|&&n| n < target).count();
+
+            match staircase_search(&matrix, &target) {
+                Ok((row, col)) => assert_eq!(matrix[row][col], target),
+                Err(rank) => {
+                    assert_eq!(rank, less);
+                    assert!(!matrix.iter().flatten().any(|&n| n == target));
+                }
+            }
+        }
+
+        assert_eq!(staircase_search(&[[1, 1], [1, 1]], &1), Ok((0, 0)));
+        assert_eq!(staircase_search::<u8, Vec<u8>>(&[], &1), Err(0));
+        assert_eq!(staircase_search(&[Vec::<u8>::new()], &1), Err(0));
+    }
+
+    #[test]
+    fn test_sorted_matrix_search() {
+        let matrix = [[1, 3, 5, 7], [10, 11, 16, 20], [23, 30, 34, 60]];
+        let flat: Vec<i32> = matrix.iter().flatten().copied().collect();
+
+        for target in 0..62 {
+            let expected = flat.binary_search(&target).map(|idx| (idx / 4, idx % 4));
+
+            assert_eq!(sorted_matrix_search(&matrix, &target), expected);
+        }
+
+        assert_eq!(sorted_matrix_search(&[[2, 2], [2, 3]], &2), Ok((0, 0)));
+        assert_eq!(sorted_matrix_search::<u8, Vec<u8>>(&[], &1), Err(0));
+        assert_eq!(sorted_matrix_search(&[Vec::<u8>::new(), Vec::new()], &1), Err(0));
+    }
+}
@@ -1,33 +1,44 @@
+use crate::binary_heap::{BinaryHeap, HeapOrder};
+use crate::sort_stats::SortStats;
+use crate::sort_trace::{trace, SortEvent, SortTrace, Tracer};
+
+/// Merges two sorted slices into one sorted vector
+///
+/// When elements compare equal the one from `left` comes first, which is what makes
+/// [`merge_sort`] stable.
 pub fn merge<T>(left: &[T], right: &[T]) -> Vec<T>
 where
-    T: PartialOrd + Copy,
+    T: PartialOrd + Clone,
 {
     let mut i = 0;
     let mut j = 0;
     let mut result = Vec::with_capacity(left.len() + right.len());
 
     while i < left.len() && j < right.len() {
-        if left[i] < right[j] {
-            result.push(left[i]);
-            i += 1;
-        } else {
-            result.push(right[j]);
+        if right[j] < left[i] {
+            result.push(right[j].clone());
             j += 1;
+        } else {
+            result.push(left[i].clone());
+            i += 1;
         }
     }
 
     if i < left.len() {
-        result.extend(&left[i..]);
+        result.extend_from_slice(&left[i..]);
     } else if j < right.len() {
-        result.extend(&right[j..]);
+        result.extend_from_slice(&right[j..]);
     }
 
     return result;
 }
 
+/// Top-down merge sort that returns a sorted copy of `arr`
+///
+/// The sort is stable: elements that compare equal keep their relative order.
 pub fn merge_sort<T>(arr: &[T]) -> Vec<T>
 where
-    T: PartialOrd + Copy,
+    T: PartialOrd + Clone,
 {
     if arr.len() <= 1 {
         return arr.to_vec();
@@ -40,14 +51,179 @@ where
     return merge(&left, &right);
 }
 
+/// Merges any number of sorted runs into one sorted vector
+///
+/// The head of every run is kept in a min-heap, so merging `k` runs of `n` elements in total
+/// takes O(n log k) time. Equal elements are taken from earlier runs first, so merging the
+/// sorted chunks of an array in order is stable.
+///
+/// # Example
+/// ```
+/// let runs = Vec::from([Vec::from([1, 4, 7]), Vec::from([2, 5]), Vec::new(), Vec::from([0, 9])]);
+///
+/// assert_eq!(algorithms_exercises::merge_sort::k_way_merge(&runs), [0, 1, 2, 4, 5, 7, 9]);
+/// ```
+pub fn k_way_merge<T, R>(runs: &[R]) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+    R: AsRef<[T]>,
+{
+    let mut result = Vec::with_capacity(runs.iter().map(|run| run.as_ref().len()).sum());
+    let mut next = vec![0; runs.len()];
+    // Ties between heads are broken by run index
+    let mut heads = BinaryHeap::new(HeapOrder::Min);
+
+    for (i, run) in runs.iter().enumerate() {
+        if let Some(head) = run.as_ref().first() {
+            heads.push((head.clone(), i));
+        }
+    }
+
+    while let Some((value, i)) = heads.pop() {
+        result.push(value);
+        next[i] += 1;
+
+        if let Some(head) = runs[i].as_ref().get(next[i]) {
+            heads.push((head.clone(), i));
+        }
+    }
+
+    return result;
+}
+
+fn merge_with_stats<T>(left: &[T], right: &[T], stats: &mut SortStats) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+{
+    let mut i = 0;
+    let mut j = 0;
+    let mut result = Vec::with_capacity(left.len() + right.len());
+
+    stats.allocate::<T>(result.capacity());
+
+    while i < left.len() && j < right.len() {
+        if stats.less(&right[j], &left[i]) {
+            result.push(right[j].clone());
+            j += 1;
+        } else {
+            result.push(left[i].clone());
+            i += 1;
+        }
+    }
+
+    if i < left.len() {
+        result.extend_from_slice(&left[i..]);
+    } else if j < right.len() {
+        result.extend_from_slice(&right[j..]);
+    }
+
+    stats.moves += result.len();
+
+    return result;
+}
+
+fn merge_sort_with_depth<T>(arr: &[T], depth: usize, stats: &mut SortStats) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+{
+    stats.enter(depth);
+
+    if arr.len() <= 1 {
+        stats.allocate::<T>(arr.len());
+        stats.moves += arr.len();
+
+        return arr.to_vec();
+    }
+
+    let mid = arr.len() / 2;
+    let left = merge_sort_with_depth(&arr[..mid], depth + 1, stats);
+    let right = merge_sort_with_depth(&arr[mid..], depth + 1, stats);
+
+    return merge_with_stats(&left, &right, stats);
+}
+
+/// [`merge_sort`] that records its comparisons, element copies, recursion depth and
+/// allocations in `stats`
+pub fn merge_sort_with_stats<T>(arr: &[T], stats: &mut SortStats) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+{
+    return merge_sort_with_depth(arr, 1, stats);
+}
+
+pub(crate) fn merge_sort_traced<T>(arr: &mut [T], lo: usize, hi: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    if hi - lo <= 1 {
+        return;
+    }
+
+    let mid = lo + (hi - lo) / 2;
+
+    merge_sort_traced(arr, lo, mid, tracer);
+    merge_sort_traced(arr, mid, hi, tracer);
+    merge_traced(arr, lo, mid, hi, tracer);
+}
+
+/// Stable merge of the sorted runs `arr[lo..mid]` and `arr[mid..hi]` back into `arr[lo..hi]`,
+/// recording the merge and its comparisons and writes
+pub(crate) fn merge_traced<T>(arr: &mut [T], lo: usize, mid: usize, hi: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    tracer.push(SortEvent::MergeRange(lo, mid, hi));
+
+    let left = arr[lo..mid].to_vec();
+    let right = arr[mid..hi].to_vec();
+    let mut i = 0;
+    let mut j = 0;
+
+    for k in lo..hi {
+        let take_right = if i < left.len() && j < right.len() {
+            tracer.compare(mid + j, lo + i);
+
+            right[j] < left[i]
+        } else {
+            j < right.len()
+        };
+
+        if take_right {
+            tracer.write(arr, k, right[j].clone());
+            j += 1;
+        } else {
+            tracer.write(arr, k, left[i].clone());
+            i += 1;
+        }
+    }
+}
+
+/// Runs [`merge_sort`] on a copy of `arr` and lends `consume` its trace, recording every merge,
+/// comparison and write
+///
+/// Each merge writes its result back into the range the two halves came from.
+pub fn merge_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| merge_sort_traced(arr, 0, arr.len(), tracer));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{generate, is_stably_sorted, tag, Distribution};
 
     #[test]
     fn test_merge() {
         assert_eq!(merge(&[1], &[5]), [1, 5]);
         assert_eq!(merge(&[7], &[4]), [4, 7]);
+
+        let tagged = tag(&[1, 2, 1, 2]);
+
+        assert!(is_stably_sorted(&merge(&tagged[..2], &tagged[2..])));
         assert_eq!(
             merge(&[1, 4, 5, 7], &[2, 3, 6]),
             [1, 2, 3, 4, 5, 6, 7]
@@ -59,5 +235,69 @@ mod tests {
         assert_eq!(merge_sort(&Vec::from([1])), [1]);
         assert_eq!(merge_sort(&Vec::from([3, 2, 1])), [1, 2, 3]);
         assert_eq!(merge_sort(&Vec::from([1, 5, 7, 4, 2, 3, 6])), [1, 2, 3, 4, 5, 6, 7]);
+
+        let keys: Vec<u64> = generate(Distribution::Random, 500, 1).iter().map(|key| key % 8).collect();
+        let mut stats = SortStats::new();
+
+        assert!(is_stably_sorted(&merge_sort(&tag(&keys))));
+        assert!(is_stably_sorted(&merge_sort_with_stats(&tag(&keys), &mut stats)));
+        assert!(merge_sort_steps(&tag(&keys), |trace| is_stably_sorted(trace.sorted())));
+    }
+
+    #[test]
+    fn test_k_way_merge() {
+        let keys: Vec<u64> = generate(Distribution::Random, 1000, 11).iter().map(|key| key % 50).collect();
+        let tagged = tag(&keys);
+        let runs: Vec<Vec<_>> = tagged.chunks(70).map(merge_sort).collect();
+
+        assert!(is_stably_sorted(&k_way_merge(&runs)));
+        assert_eq!(k_way_merge(&runs).len(), 1000);
+        assert_eq!(k_way_merge::<u8, Vec<u8>>(&[]), []);
+        assert_eq!(k_way_merge(&[[3, 5], [1, 4]]), [1, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_merge_sort_with_stats() {
+        let mut stats = SortStats::new();
+
+        assert_eq!(merge_sort_with_stats(&[3u32, 2, 1], &mut stats), [1, 2, 3]);
+        assert_eq!(
+            stats,
+            SortStats {
+                comparisons: 3,
+                swaps: 0,
+                moves: 8,
+                max_depth: 3,
+                bytes_allocated: 32,
+            }
+        );
+    }
+
+    #[test]
+    fn test_merge_sort_steps() {
+        let (traced, events) = merge_sort_steps(&[3, 1, 2], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::MergeRange(1, 2, 3),
+                SortEvent::Compare(2, 1),
+                SortEvent::Write(1, 1),
+                SortEvent::Write(2, 2),
+                SortEvent::MergeRange(0, 1, 3),
+                SortEvent::Compare(1, 0),
+                SortEvent::Write(0, 1),
+                SortEvent::Compare(2, 0),
+                SortEvent::Write(1, 2),
+                SortEvent::Write(2, 3),
+            ]
+        );
+
+        let nums = Vec::from([1, 5, 7, 4, 2, 3, 6]);
+        let mut replayed = nums.clone();
+
+        merge_sort_steps(&nums, |trace| replay(&mut replayed, trace));
+        assert_eq!(replayed, merge_sort(&nums));
     }
 }
@@ -1,25 +1,810 @@
+use std::error::Error;
+use std::fmt;
+use std::iter::{Peekable, Sum};
+use std::mem;
+use std::ops::Mul;
+use std::slice;
+use std::str::{Chars, FromStr};
+
+/// Arbitrarily nested list of values, like `[1, [2, [3, 4]], 5]`
+///
+/// Every operation, including dropping, comparing and formatting, walks the array with an
+/// explicit stack instead of recursing, so arrays nested millions of levels deep don't overflow
+/// the call stack.
 pub enum NestedArray<T> {
     Value(T),
     Array(Vec<NestedArray<T>>),
 }
 
-pub fn nested_add(arr: &Vec<NestedArray<usize>>) -> usize {
-    let mut result = 0;
+/// Iterator over the values of a [`NestedArray`] with their depths, see [`NestedArray::leaves`]
+pub struct Leaves<'a, T> {
+    /// The remaining items of every array the iterator is in, outermost first
+    stack: Vec<slice::Iter<'a, NestedArray<T>>>,
+}
+
+impl<'a, T> Iterator for Leaves<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.stack.last_mut()?.next() {
+                None => {
+                    self.stack.pop();
+                }
+                Some(NestedArray::Value(value)) => return Some((self.stack.len() - 1, value)),
+                Some(NestedArray::Array(items)) => self.stack.push(items.iter()),
+            }
+        }
+    }
+}
+
+impl<T> NestedArray<T> {
+    /// Every value from left to right with the number of arrays around it
+    ///
+    /// # Example
+    /// ```
+    /// use algorithms_exercises::nested_add::NestedArray::{Array, Value};
+    ///
+    /// let nested = Array(Vec::from([Value('a'), Array(Vec::from([Value('b')]))]));
+    ///
+    /// assert_eq!(nested.leaves().collect::<Vec<_>>(), [(1, &'a'), (2, &'b')]);
+    /// ```
+    pub fn leaves(&self) -> Leaves<'_, T> {
+        return Leaves {
+            stack: Vec::from([slice::from_ref(self).iter()]),
+        };
+    }
+
+    /// Every value from left to right
+    pub fn flatten(&self) -> impl Iterator<Item = &T> {
+        return self.leaves().map(|(_, value)| value);
+    }
+
+    /// Combines every value from left to right into an accumulator, like `Iterator::fold`
+    pub fn fold<B, F>(&self, init: B, f: F) -> B
+    where
+        F: FnMut(B, &T) -> B,
+    {
+        return self.flatten().fold(init, f);
+    }
+
+    /// Same structure with `f` applied to every value
+    pub fn map<U, F>(&self, mut f: F) -> NestedArray<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        let items = match self {
+            NestedArray::Value(value) => return NestedArray::Value(f(value)),
+            NestedArray::Array(items) => items,
+        };
+        // The remaining items of every array being mapped with the items mapped so far
+        let mut open = Vec::from([(items.iter(), Vec::with_capacity(items.len()))]);
+
+        loop {
+            let (remaining, mapped) = open.last_mut().unwrap();
+
+            match remaining.next() {
+                Some(NestedArray::Value(value)) => mapped.push(NestedArray::Value(f(value))),
+                Some(NestedArray::Array(items)) => open.push((items.iter(), Vec::with_capacity(items.len()))),
+                None => {
+                    let array = NestedArray::Array(open.pop().unwrap().1);
+
+                    match open.last_mut() {
+                        Some((_, mapped)) => mapped.push(array),
+                        None => return array,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Number of levels of arrays, counting empty ones: 0 for a value, 1 for a flat array
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut stack = Vec::from([(self, 0)]);
+
+        while let Some((item, level)) = stack.pop() {
+            if let NestedArray::Array(items) = item {
+                depth = depth.max(level + 1);
+                stack.extend(items.iter().map(|item| (item, level + 1)));
+            }
+        }
+
+        return depth;
+    }
+
+    /// Writes the array with `open` and `close` around every array and `", "` between items
+    fn write_nested<F>(&self, f: &mut fmt::Formatter, open: &str, close: &str, mut write_value: F) -> fmt::Result
+    where
+        F: FnMut(&mut fmt::Formatter, &T) -> fmt::Result,
+    {
+        let mut stack = Vec::from([slice::from_ref(self).iter()]);
+        let mut first = true;
+
+        while let Some(remaining) = stack.last_mut() {
+            let Some(item) = remaining.next() else {
+                stack.pop();
+
+                // Everything below the bottom of the stack, which only holds `self`, is an array
+                if !stack.is_empty() {
+                    f.write_str(close)?;
+                }
+
+                first = false;
+                continue;
+            };
+
+            if !first {
+                f.write_str(", ")?;
+            }
+
+            match item {
+                NestedArray::Value(value) => {
+                    write_value(f, value)?;
+                    first = false;
+                }
+                NestedArray::Array(items) => {
+                    f.write_str(open)?;
+                    stack.push(items.iter());
+                    first = true;
+                }
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn count_leaves(&self) -> usize {
+        return self.leaves().count();
+    }
+
+    /// Depth of the most deeply nested value, or `None` if there are no values
+    ///
+    /// Unlike [`depth`](NestedArray::depth) this ignores arrays with no values inside.
+    pub fn max_depth(&self) -> Option<usize> {
+        return self.leaves().map(|(depth, _)| depth).max();
+    }
+
+    /// Sum of every value multiplied by `weight` of its depth
+    ///
+    /// # Example
+    /// ```
+    /// use algorithms_exercises::nested_add::NestedArray::{Array, Value};
+    ///
+    /// // [1, [4, [6]]]
+    /// let nested = Array(Vec::from([Value(1), Array(Vec::from([Value(4), Array(Vec::from([Value(6)]))]))]));
+    ///
+    /// assert_eq!(nested.weighted_sum(|depth| depth), 1 + 4 * 2 + 6 * 3);
+    ///
+    /// // Weighted the other way round, the deepest values count once
+    /// let max_depth = nested.max_depth().unwrap();
+    ///
+    /// assert_eq!(nested.weighted_sum(|depth| max_depth - depth + 1), 1 * 3 + 4 * 2 + 6);
+    /// ```
+    pub fn weighted_sum<F>(&self, mut weight: F) -> T
+    where
+        T: Copy + Mul<Output = T> + Sum<T>,
+        F: FnMut(usize) -> T,
+    {
+        return self.leaves().map(|(depth, &value)| value * weight(depth)).sum();
+    }
+}
+
+impl<T> Drop for NestedArray<T> {
+    /// Moves the items of nested arrays onto a stack before dropping them, so that no array
+    /// that gets dropped still has arrays inside
+    fn drop(&mut self) {
+        let NestedArray::Array(items) = self else {
+            return;
+        };
+        let mut stack = mem::take(items);
+
+        while let Some(mut item) = stack.pop() {
+            if let NestedArray::Array(items) = &mut item {
+                stack.append(items);
+            }
+        }
+    }
+}
+
+impl<T: Clone> Clone for NestedArray<T> {
+    fn clone(&self) -> Self {
+        return self.map(T::clone);
+    }
+}
+
+impl<T: PartialEq> PartialEq for NestedArray<T> {
+    fn eq(&self, other: &Self) -> bool {
+        let mut stack = Vec::from([(self, other)]);
+
+        while let Some(pair) = stack.pop() {
+            match pair {
+                (NestedArray::Value(a), NestedArray::Value(b)) if a == b => {}
+                (NestedArray::Array(a), NestedArray::Array(b)) if a.len() == b.len() => stack.extend(a.iter().zip(b)),
+                _ => return false,
+            }
+        }
+
+        return true;
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for NestedArray<T> {
+    /// Writes the array like `Array([Value(1), Array([])])`, always on one line
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return self.write_nested(f, "Array([", "])", |f, value| write!(f, "Value({:?})", value));
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for NestedArray<T> {
+    /// Writes the array as bracket text like `[1, [2, 3]]`, which parses back into the same
+    /// array as long as the values' own text does
+    ///
+    /// Values whose text is empty, has whitespace around it, contains a bracket or a comma or
+    /// starts with a quote are written in double quotes, with `\"` and `\\` escaping quotes and
+    /// backslashes inside them.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return self.write_nested(f, "[", "]", |f, value| write_value(f, &value.to_string()));
+    }
+}
+
+/// Writes the text of a value, quoting it when it wouldn't parse back on its own
+fn write_value(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    let needs_quotes = text.is_empty()
+        || text.starts_with(char::is_whitespace)
+        || text.ends_with(char::is_whitespace)
+        || text.starts_with('"')
+        || text.contains(['[', ']', ',']);
+
+    if !needs_quotes {
+        return f.write_str(text);
+    }
+
+    f.write_str("\"")?;
+
+    for c in text.chars() {
+        if matches!(c, '"' | '\\') {
+            f.write_str("\\")?;
+        }
+
+        write!(f, "{}", c)?;
+    }
+
+    return f.write_str("\"");
+}
+
+/// Error from parsing a [`NestedArray`], with the position in the text it was found at
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseNestedArrayError {
+    /// Line of the error, counting from 1
+    pub line: usize,
+    /// Character in the line of the error, counting from 1
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseNestedArrayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "line {}, column {}: {}", self.line, self.column, self.message);
+    }
+}
+
+impl Error for ParseNestedArrayError {}
+
+/// Characters of the text being parsed, keeping track of the current line and column
+struct Cursor<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+    column: usize,
+}
+
+impl Cursor<'_> {
+    fn peek(&mut self) -> Option<char> {
+        return self.chars.peek().copied();
+    }
+
+    fn bump(&mut self) {
+        if self.chars.next() == Some('\n') {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.bump();
+        }
+    }
+
+    fn error(&self, message: String) -> ParseNestedArrayError {
+        return ParseNestedArrayError {
+            line: self.line,
+            column: self.column,
+            message,
+        };
+    }
+
+    /// Error for the next character, which was not what the parser expected
+    fn unexpected(&mut self, expected: &str) -> ParseNestedArrayError {
+        let found = match self.peek() {
+            Some(c) => format!("`{}`", c),
+            None => String::from("end of input"),
+        };
+
+        return self.error(format!("expected {}, found {}", expected, found));
+    }
+
+    /// Parses the text of a quoted value, after its opening quote, up to its closing quote
+    fn quoted(&mut self) -> Result<String, ParseNestedArrayError> {
+        let mut text = String::new();
+
+        loop {
+            match self.peek() {
+                Some('"') => {
+                    self.bump();
+                    return Ok(text);
+                }
+                Some('\\') => {
+                    self.bump();
+
+                    match self.peek() {
+                        Some(c @ ('"' | '\\')) => text.push(c),
+                        _ => return Err(self.unexpected("`\"` or `\\` after `\\`")),
+                    }
+                }
+                Some(c) => text.push(c),
+                None => return Err(self.unexpected("`\"`")),
+            }
+
+            self.bump();
+        }
+    }
+
+    /// Parses a quoted value, or the text up to the next bracket or comma, as a value
+    fn value<T: FromStr>(&mut self) -> Result<T, ParseNestedArrayError> {
+        let (line, column) = (self.line, self.column);
+        let mut text = String::new();
+
+        if self.peek() == Some('"') {
+            self.bump();
+            text = self.quoted()?;
+        } else {
+            while let Some(c) = self.peek().filter(|c| !matches!(c, '[' | ']' | ',')) {
+                text.push(c);
+                self.bump();
+            }
+
+            text.truncate(text.trim_end().len());
+        }
+
+        return text.parse().map_err(|_| ParseNestedArrayError {
+            line,
+            column,
+            message: format!("invalid value `{}`", text),
+        });
+    }
+}
+
+impl<T: FromStr> FromStr for NestedArray<T> {
+    type Err = ParseNestedArrayError;
+
+    /// Parses bracket text like `[1, [2, 3]]`, the format [`Display`](fmt::Display) writes
+    ///
+    /// Values are the text between brackets and commas with the surrounding whitespace trimmed,
+    /// or text in double quotes, which may contain anything, with `\"` and `\\` standing for a
+    /// quote and a backslash.
+    ///
+    /// # Example
+    /// ```
+    /// use algorithms_exercises::nested_add::NestedArray;
+    ///
+    /// let nested: NestedArray<u32> = "[1, [2, [3, 4]], 5]".parse().unwrap();
+    ///
+    /// assert_eq!(nested.count_leaves(), 5);
+    /// assert_eq!(nested.to_string(), "[1, [2, [3, 4]], 5]");
+    ///
+    /// let error = "[1,\n [2 3]]".parse::<NestedArray<u32>>().unwrap_err();
+    ///
+    /// assert_eq!(error.to_string(), "line 2, column 3: invalid value `2 3`");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cursor = Cursor {
+            chars: s.chars().peekable(),
+            line: 1,
+            column: 1,
+        };
+        // Items parsed so far of every array that has been opened but not closed yet
+        let mut open: Vec<Vec<NestedArray<T>>> = Vec::new();
+
+        loop {
+            cursor.skip_whitespace();
+
+            let mut item = match cursor.peek() {
+                Some('[') => {
+                    cursor.bump();
+                    cursor.skip_whitespace();
+
+                    if cursor.peek() != Some(']') {
+                        open.push(Vec::new());
+                        continue;
+                    }
+
+                    cursor.bump();
+                    NestedArray::Array(Vec::new())
+                }
+                Some(']') | Some(',') | None => return Err(cursor.unexpected("a value")),
+                Some(_) => NestedArray::Value(cursor.value()?),
+            };
+
+            // Add the finished item to its array, closing every array that ends after it
+            loop {
+                cursor.skip_whitespace();
+
+                let Some(items) = open.last_mut() else {
+                    if cursor.peek().is_some() {
+                        return Err(cursor.unexpected("end of input"));
+                    }
+
+                    return Ok(item);
+                };
+
+                items.push(item);
+
+                match cursor.peek() {
+                    Some(',') => {
+                        cursor.bump();
+                        break;
+                    }
+                    Some(']') => {
+                        cursor.bump();
+                        item = NestedArray::Array(open.pop().unwrap());
+                    }
+                    _ => return Err(cursor.unexpected("`,` or `]`")),
+                }
+            }
+        }
+    }
+}
+
+/// Sum of every value in `arr`, however deeply nested
+///
+/// Overflowing `usize` panics in debug builds and wraps in release builds, see
+/// [`checked_nested_add`] and the other variants for sums that may not fit.
+pub fn nested_add(arr: &[NestedArray<usize>]) -> usize {
+    return arr.iter().map(|item| item.fold(0, |sum, value| sum + value)).sum();
+}
+
+/// Integer type the `usize` values of a [`NestedArray`] can be summed into, such as `u128` or
+/// [`BigUint`](crate::big_int::BigUint) for sums that never overflow
+pub trait Accumulator: Sized {
+    fn zero() -> Self;
+
+    /// `self + value`, or `None` if that doesn't fit
+    fn checked_accumulate(self, value: usize) -> Option<Self>;
+
+    /// `self + value`, or the largest value of the type if that doesn't fit
+    fn saturating_accumulate(self, value: usize) -> Self;
+
+    /// `self + value`, wrapping around at the largest value of the type
+    fn wrapping_accumulate(self, value: usize) -> Self;
+}
+
+macro_rules! impl_accumulator {
+    ($($t:ty),*) => {
+        $(
+            impl Accumulator for $t {
+                fn zero() -> Self {
+                    return 0;
+                }
+
+                fn checked_accumulate(self, value: usize) -> Option<Self> {
+                    // A value that doesn't fit in the accumulator overflows it on its own
+                    return self.checked_add(<$t>::try_from(value).ok()?);
+                }
+
+                fn saturating_accumulate(self, value: usize) -> Self {
+                    return <$t>::try_from(value).map_or(<$t>::MAX, |value| self.saturating_add(value));
+                }
+
+                fn wrapping_accumulate(self, value: usize) -> Self {
+                    // Truncating keeps `value` modulo 2^bits, which is all a wrapping sum needs
+                    return self.wrapping_add(value as $t);
+                }
+            }
+        )*
+    };
+}
+
+impl_accumulator!(usize, u64, u128);
+
+/// Error from [`checked_nested_add`] when the sum doesn't fit in the accumulator
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NestedAddOverflow {
+    /// Indices leading to the value that made the sum overflow, starting in the outermost array
+    pub path: Vec<usize>,
+}
+
+impl fmt::Display for NestedAddOverflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sum overflows at ")?;
+
+        for i in &self.path {
+            write!(f, "[{}]", i)?;
+        }
+
+        return Ok(());
+    }
+}
+
+impl Error for NestedAddOverflow {}
+
+/// Sum of every value in `arr` as an `A`, or an error with the path to the value that made it
+/// overflow
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::nested_add::{checked_nested_add, NestedArray};
+///
+/// let arr: NestedArray<usize> = format!("[1, [2, {}], 3]", usize::MAX).parse().unwrap();
+/// let NestedArray::Array(items) = &arr else { unreachable!() };
+///
+/// assert_eq!(checked_nested_add::<usize>(items).unwrap_err().path, [1, 1]);
+/// assert_eq!(checked_nested_add::<u128>(items), Ok(usize::MAX as u128 + 6));
+/// ```
+pub fn checked_nested_add<A: Accumulator>(arr: &[NestedArray<usize>]) -> Result<A, NestedAddOverflow> {
+    let mut sum = A::zero();
+    // Every array the walk is in with the index of its next item
+    let mut stack = Vec::from([(arr, 0)]);
+
+    while let Some((items, next)) = stack.last_mut() {
+        let Some(item) = items.get(*next) else {
+            stack.pop();
+            continue;
+        };
+
+        *next += 1;
 
-    for item in arr {
         match item {
-            NestedArray::Value(v) => result += v,
-            NestedArray::Array(a) => result += nested_add(a),
+            NestedArray::Value(value) => {
+                sum = sum.checked_accumulate(*value).ok_or_else(|| NestedAddOverflow {
+                    path: stack.iter().map(|(_, next)| next - 1).collect(),
+                })?;
+            }
+            NestedArray::Array(items) => stack.push((items, 0)),
         }
     }
 
-    return result;
+    return Ok(sum);
+}
+
+/// Sum of every value in `arr` as an `A`, stopping at the largest value of `A`
+pub fn saturating_nested_add<A: Accumulator>(arr: &[NestedArray<usize>]) -> A {
+    return arr.iter().flat_map(NestedArray::flatten).fold(A::zero(), |sum, &value| sum.saturating_accumulate(value));
+}
+
+/// Sum of every value in `arr` as an `A`, wrapping around at the largest value of `A`
+pub fn wrapping_nested_add<A: Accumulator>(arr: &[NestedArray<usize>]) -> A {
+    return arr.iter().flat_map(NestedArray::flatten).fold(A::zero(), |sum, &value| sum.wrapping_accumulate(value));
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn sample() -> NestedArray<i32> {
+        // [1, [2, [3, 4], []], 5]
+        return NestedArray::Array(Vec::from([
+            NestedArray::Value(1),
+            NestedArray::Array(Vec::from([
+                NestedArray::Value(2),
+                NestedArray::Array(Vec::from([NestedArray::Value(3), NestedArray::Value(4)])),
+                NestedArray::Array(Vec::new()),
+            ])),
+            NestedArray::Value(5),
+        ]));
+    }
+
+    #[test]
+    fn test_leaves() {
+        assert_eq!(sample().leaves().collect::<Vec<_>>(), [(1, &1), (2, &2), (3, &3), (3, &4), (1, &5)]);
+        assert_eq!(NestedArray::Value(7).leaves().collect::<Vec<_>>(), [(0, &7)]);
+        assert_eq!(NestedArray::<i32>::Array(Vec::new()).leaves().count(), 0);
+    }
+
+    #[test]
+    fn test_flatten() {
+        assert_eq!(sample().flatten().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(sample().fold(0, |sum, n| sum + n), 15);
+        assert_eq!(sample().fold(1, |product, n| product * n), 120);
+        assert_eq!(sample().fold(String::new(), |s, n| s + &n.to_string()), "12345");
+    }
+
+    #[test]
+    fn test_map() {
+        let strings = sample().map(|n| n.to_string());
+
+        assert_eq!(strings.flatten().cloned().collect::<Vec<_>>(), ["1", "2", "3", "4", "5"]);
+        assert_eq!(strings.depth(), sample().depth());
+        assert_eq!(NestedArray::Value(2).map(|n| n * 10), NestedArray::Value(20));
+    }
+
+    #[test]
+    fn test_depth() {
+        assert_eq!(sample().depth(), 3);
+        assert_eq!(NestedArray::Value(1).depth(), 0);
+        assert_eq!(NestedArray::<i32>::Array(Vec::new()).depth(), 1);
+        assert_eq!(NestedArray::<i32>::Array(Vec::from([NestedArray::Array(Vec::new())])).depth(), 2);
+    }
+
+    #[test]
+    fn test_count_leaves() {
+        assert_eq!(sample().count_leaves(), 5);
+        assert_eq!(NestedArray::Value(1).count_leaves(), 1);
+    }
+
+    #[test]
+    fn test_max_depth() {
+        assert_eq!(sample().max_depth(), Some(3));
+        assert_eq!(NestedArray::Value(1).max_depth(), Some(0));
+        assert_eq!(NestedArray::<i32>::Array(Vec::from([NestedArray::Array(Vec::new())])).max_depth(), None);
+    }
+
+    #[test]
+    fn test_weighted_sum() {
+        assert_eq!(sample().weighted_sum(|depth| depth as i32), 1 + 2 * 2 + 3 * 3 + 4 * 3 + 5);
+        assert_eq!(sample().weighted_sum(|_| 1), 15);
+        assert_eq!(NestedArray::Value(2.5).weighted_sum(|depth| depth as f64 + 2.0), 5.0);
+    }
+
+    fn parse_error(s: &str) -> (usize, usize, String) {
+        let error = s.parse::<NestedArray<i32>>().unwrap_err();
+
+        return (error.line, error.column, error.message);
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!("[1, [2, [3, 4], []], 5]".parse(), Ok(sample()));
+        assert_eq!(" \n[1,[2,[ 3 ,4 ],[ ]]\n, 5]\t".parse(), Ok(sample()));
+        assert_eq!("-7".parse(), Ok(NestedArray::Value(-7)));
+        assert_eq!("[]".parse(), Ok(NestedArray::<i32>::Array(Vec::new())));
+        assert_eq!(
+            "[0.5, [NaN]]".parse::<NestedArray<f64>>().map(|nested| nested.count_leaves()),
+            Ok(2)
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(parse_error(""), (1, 1, String::from("expected a value, found end of input")));
+        assert_eq!(parse_error("[1, 2"), (1, 6, String::from("expected `,` or `]`, found end of input")));
+        assert_eq!(parse_error("[1, 2,]"), (1, 7, String::from("expected a value, found `]`")));
+        assert_eq!(parse_error("[1 [2]]"), (1, 4, String::from("expected `,` or `]`, found `[`")));
+        assert_eq!(parse_error("[1]]"), (1, 4, String::from("expected end of input, found `]`")));
+        assert_eq!(parse_error("[1,\n  [x]]"), (2, 4, String::from("invalid value `x`")));
+        assert_eq!(parse_error("[\n\n, 1]"), (3, 1, String::from("expected a value, found `,`")));
+        assert_eq!(parse_error("[99999999999]"), (1, 2, String::from("invalid value `99999999999`")));
+        assert_eq!(parse_error("[\"1]"), (1, 5, String::from("expected `\"`, found end of input")));
+        assert_eq!(parse_error("[\"1\\n\"]"), (1, 5, String::from("expected `\"` or `\\` after `\\`, found `n`")));
+        assert_eq!(parse_error("[\"1\" 2]"), (1, 6, String::from("expected `,` or `]`, found `2`")));
+        assert_eq!(parse_error("[\"x\"]"), (1, 2, String::from("invalid value `x`")));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(sample().to_string(), "[1, [2, [3, 4], []], 5]");
+        assert_eq!(NestedArray::Value(3).to_string(), "3");
+
+        let words: NestedArray<String> = "[a, [b c, [d]], []]".parse().unwrap();
+
+        assert_eq!(words.to_string(), "[a, [b c, [d]], []]");
+        assert_eq!(words.to_string().parse(), Ok(words));
+
+        let quoted = NestedArray::Array(
+            [",", "]", "", " padded ", "\"quoted\"", "a\"b", "back\\slash", "[x]"]
+                .map(|text| NestedArray::Value(String::from(text)))
+                .into(),
+        );
+        let text = quoted.to_string();
+
+        assert_eq!(text, r#"[",", "]", "", " padded ", "\"quoted\"", a"b, back\slash, "[x]"]"#);
+        assert_eq!(text.parse(), Ok(quoted));
+        assert_eq!(r#"[ "a, b" , "\\" ]"#.parse(), Ok(NestedArray::Array(vec![
+            NestedArray::Value(String::from("a, b")),
+            NestedArray::Value(String::from("\\")),
+        ])));
+    }
+
+    #[test]
+    fn test_eq() {
+        let mut other = sample();
+
+        assert_eq!(other, sample());
+
+        if let NestedArray::Array(items) = &mut other {
+            items[1] = NestedArray::Array(Vec::from([NestedArray::Value(2)]));
+        }
+
+        assert_ne!(other, sample());
+        assert_ne!(NestedArray::Value(1), NestedArray::Array(Vec::from([NestedArray::Value(1)])));
+        assert_ne!(NestedArray::Array(Vec::from([NestedArray::Value(1)])), NestedArray::Array(Vec::new()));
+    }
+
+    #[test]
+    fn test_debug() {
+        assert_eq!(
+            format!("{:?}", sample()),
+            "Array([Value(1), Array([Value(2), Array([Value(3), Value(4)]), Array([])]), Value(5)])"
+        );
+        assert_eq!(format!("{:?}", NestedArray::Value("a")), "Value(\"a\")");
+    }
+
+    #[test]
+    fn test_deeply_nested() {
+        const LEVELS: usize = 1_000_000;
+
+        let mut nested = NestedArray::Value(1);
+
+        for _ in 0..LEVELS {
+            nested = NestedArray::Array(Vec::from([nested, NestedArray::Value(1)]));
+        }
+
+        let text = nested.to_string();
+
+        assert_eq!(text.len(), LEVELS * 5 + 1);
+        assert_eq!(nested.depth(), LEVELS);
+        assert_eq!(nested.max_depth(), Some(LEVELS));
+        assert_eq!(nested.count_leaves(), LEVELS + 1);
+        assert_eq!(nested.map(|n| n * 2).fold(0, |sum, n| sum + n), 2 * (LEVELS + 1));
+        assert_eq!(text.parse(), Ok(nested.clone()));
+        assert!(format!("{:?}", nested).starts_with("Array([Array([Array(["));
+        assert_eq!(nested_add(&[nested]), LEVELS + 1);
+    }
+
+    fn large() -> Vec<NestedArray<usize>> {
+        // [MAX, [[1], [2, MAX]]]
+        return Vec::from([
+            NestedArray::Value(usize::MAX),
+            NestedArray::Array(Vec::from([
+                NestedArray::Array(Vec::from([NestedArray::Value(1)])),
+                NestedArray::Array(Vec::from([NestedArray::Value(2), NestedArray::Value(usize::MAX)])),
+            ])),
+        ]);
+    }
+
+    #[test]
+    fn test_checked_nested_add() {
+        let overflow = checked_nested_add::<usize>(&large()).unwrap_err();
+
+        assert_eq!(overflow.path, [1, 0, 0]);
+        assert_eq!(overflow.to_string(), "sum overflows at [1][0][0]");
+        assert_eq!(checked_nested_add::<u128>(&large()), Ok(2 * usize::MAX as u128 + 3));
+        assert_eq!(checked_nested_add::<usize>(&[]), Ok(0));
+
+        let small: NestedArray<usize> = "[[1, 2], [], [[3]]]".parse().unwrap();
+
+        assert_eq!(checked_nested_add::<u64>(slice::from_ref(&small)), Ok(6));
+    }
+
+    #[test]
+    fn test_saturating_nested_add() {
+        assert_eq!(saturating_nested_add::<usize>(&large()), usize::MAX);
+        assert_eq!(saturating_nested_add::<u128>(&large()), 2 * usize::MAX as u128 + 3);
+    }
+
+    #[test]
+    fn test_wrapping_nested_add() {
+        // MAX + 1 wraps to 0, then 2 + MAX wraps to 1
+        assert_eq!(wrapping_nested_add::<usize>(&large()), 1);
+        assert_eq!(wrapping_nested_add::<u128>(&large()), 2 * usize::MAX as u128 + 3);
+    }
+
     #[test]
     fn test_nested_add() {
         let nums1: Vec<NestedArray<usize>> = Vec::new();
@@ -0,0 +1,429 @@
+use std::mem::MaybeUninit;
+use std::thread;
+
+use crate::merge_sort::{merge_sort, merge_sort_traced, merge_traced};
+use crate::quick_sort::{median_of_three, partition, partition_traced, quick_sort_in_place, quick_sort_in_place_traced};
+use crate::radix_sort::{count_bytes, lsd_radix_sort, lsd_radix_sort_traced, lsd_radix_sort_with_counts, RadixKey};
+use crate::sort_trace::{trace, SortTrace, Tracer};
+
+/// How much parallelism the parallel sorts may use
+///
+/// Work is split recursively and every split halves the thread budget, so at most `threads`
+/// threads run at once. Inputs (or sub-problems) of at most `sequential_cutoff` elements are
+/// handled by the sequential algorithm, since spawning a thread costs more than sorting them.
+#[derive(Clone, Copy, Debug)]
+pub struct ParallelConfig {
+    pub threads: usize,
+    pub sequential_cutoff: usize,
+}
+
+impl ParallelConfig {
+    pub fn new(threads: usize, sequential_cutoff: usize) -> Self {
+        return Self {
+            threads,
+            sequential_cutoff,
+        };
+    }
+}
+
+impl Default for ParallelConfig {
+    /// One thread per available core and a cutoff of 4096 elements
+    fn default() -> Self {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+
+        return Self::new(threads, 4096);
+    }
+}
+
+/// Sequential merge of `left` and `right` into `out`, taking from `left` on ties
+fn merge_into<T>(left: &[T], right: &[T], out: &mut [MaybeUninit<T>])
+where
+    T: PartialOrd + Clone,
+{
+    let mut i = 0;
+    let mut j = 0;
+
+    for slot in out.iter_mut() {
+        if j == right.len() || (i < left.len() && left[i] <= right[j]) {
+            slot.write(left[i].clone());
+            i += 1;
+        } else {
+            slot.write(right[j].clone());
+            j += 1;
+        }
+    }
+}
+
+/// Merges `left` and `right` into `out` by splitting both at a common value and merging the two
+/// halves on separate threads
+fn parallel_merge<T>(left: &[T], right: &[T], out: &mut [MaybeUninit<T>], threads: usize, cutoff: usize)
+where
+    T: PartialOrd + Clone + Send + Sync,
+{
+    if threads <= 1 || out.len() <= cutoff.max(1) {
+        merge_into(left, right, out);
+        return;
+    }
+
+    // Equal keys from `left` must stay in front of those from `right`, so the split point in
+    // `right` excludes values equal to the pivot and the one in `left` includes them
+    let (l, r) = if left.len() >= right.len() {
+        let l = left.len() / 2;
+
+        (l, right.partition_point(|x| *x < left[l]))
+    } else {
+        let r = right.len() / 2;
+
+        (left.partition_point(|x| *x <= right[r]), r)
+    };
+
+    let (low, high) = out.split_at_mut(l + r);
+
+    thread::scope(|s| {
+        s.spawn(|| parallel_merge(&left[..l], &right[..r], low, threads / 2, cutoff));
+        parallel_merge(&left[l..], &right[r..], high, threads - threads / 2, cutoff);
+    });
+}
+
+fn parallel_merge_sort_with<T>(arr: &[T], threads: usize, cutoff: usize) -> Vec<T>
+where
+    T: PartialOrd + Clone + Send + Sync,
+{
+    if threads <= 1 || arr.len() <= cutoff.max(1) {
+        return merge_sort(arr);
+    }
+
+    let mid = arr.len() / 2;
+    let (left, right) = thread::scope(|s| {
+        let left = s.spawn(|| parallel_merge_sort_with(&arr[..mid], threads / 2, cutoff));
+        let right = parallel_merge_sort_with(&arr[mid..], threads - threads / 2, cutoff);
+
+        return (left.join().unwrap(), right);
+    });
+    let mut result = Vec::with_capacity(arr.len());
+
+    parallel_merge(&left, &right, &mut result.spare_capacity_mut()[..arr.len()], threads, cutoff);
+
+    // SAFETY: `parallel_merge` wrote every one of the first `arr.len()` slots, if a clone panics
+    // it unwinds past this point and the written elements are merely leaked
+    unsafe { result.set_len(arr.len()) };
+
+    return result;
+}
+
+/// Merge sort that sorts both halves and merges them on separate threads
+///
+/// Like `merge_sort`, it returns a sorted copy and the sort is stable.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::parallel_sort::{parallel_merge_sort, ParallelConfig};
+///
+/// let nums: Vec<usize> = (0..1000).rev().collect();
+/// let sorted = parallel_merge_sort(&nums, &ParallelConfig::new(4, 64));
+///
+/// assert_eq!(sorted, (0..1000).collect::<Vec<_>>());
+/// ```
+pub fn parallel_merge_sort<T>(arr: &[T], config: &ParallelConfig) -> Vec<T>
+where
+    T: PartialOrd + Clone + Send + Sync,
+{
+    return parallel_merge_sort_with(arr, config.threads, config.sequential_cutoff);
+}
+
+fn parallel_quick_sort_with<T>(arr: &mut [T], threads: usize, cutoff: usize)
+where
+    T: PartialOrd + Send,
+{
+    if threads <= 1 || arr.len() <= cutoff.max(1) {
+        quick_sort_in_place(arr);
+        return;
+    }
+
+    let (lt, gt) = partition(arr, median_of_three(arr));
+    let (left, rest) = arr.split_at_mut(lt);
+    let right = &mut rest[gt - lt..];
+
+    thread::scope(|s| {
+        s.spawn(|| parallel_quick_sort_with(left, threads / 2, cutoff));
+        parallel_quick_sort_with(right, threads - threads / 2, cutoff);
+    });
+}
+
+/// In-place quick sort that sorts the two sides of every partition on separate threads
+///
+/// The sort is not stable, see [`parallel_merge_sort`] for a stable parallel sort.
+pub fn parallel_quick_sort<T>(arr: &mut [T], config: &ParallelConfig)
+where
+    T: PartialOrd + Send,
+{
+    parallel_quick_sort_with(arr, config.threads, config.sequential_cutoff);
+}
+
+/// LSD radix sort whose byte histograms are counted on separate threads
+///
+/// The input is split into one chunk per thread, each thread counts its chunk and the
+/// histograms are summed before the (sequential) counting passes run. The sort is stable.
+pub fn parallel_radix_sort<T>(arr: &mut [T], config: &ParallelConfig)
+where
+    T: RadixKey + Send + Sync,
+{
+    if config.threads <= 1 || arr.len() <= config.sequential_cutoff.max(1) {
+        lsd_radix_sort(arr);
+        return;
+    }
+
+    let chunk_size = arr.len().div_ceil(config.threads);
+    let chunk_counts: Vec<Vec<[usize; 256]>> = thread::scope(|s| {
+        let handles: Vec<_> = arr
+            .chunks(chunk_size)
+            .map(|chunk| s.spawn(move || count_bytes(chunk)))
+            .collect();
+
+        return handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    });
+    let mut counts = vec![[0; 256]; T::BYTES];
+
+    for chunk in &chunk_counts {
+        for (total, count) in counts.iter_mut().zip(chunk) {
+            for (t, c) in total.iter_mut().zip(count) {
+                *t += c;
+            }
+        }
+    }
+
+    lsd_radix_sort_with_counts(arr, &counts);
+}
+
+fn parallel_merge_sort_traced<T>(arr: &mut [T], lo: usize, hi: usize, threads: usize, cutoff: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    if threads <= 1 || hi - lo <= cutoff.max(1) {
+        merge_sort_traced(arr, lo, hi, tracer);
+        return;
+    }
+
+    let mid = lo + (hi - lo) / 2;
+
+    parallel_merge_sort_traced(arr, lo, mid, threads / 2, cutoff, tracer);
+    parallel_merge_sort_traced(arr, mid, hi, threads - threads / 2, cutoff, tracer);
+    merge_traced(arr, lo, mid, hi, tracer);
+}
+
+/// Runs [`parallel_merge_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// merge, comparison and write
+///
+/// The sort is split the way `config` splits it, but runs on a single thread: the halves are
+/// reported one after the other and every parallel merge as the sequential merge it is equivalent
+/// to.
+pub fn parallel_merge_sort_steps<T, C, R>(arr: &[T], config: &ParallelConfig, consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    let (threads, cutoff) = (config.threads, config.sequential_cutoff);
+
+    return trace(arr, consume, move |arr, tracer| parallel_merge_sort_traced(arr, 0, arr.len(), threads, cutoff, tracer));
+}
+
+fn parallel_quick_sort_traced<T>(arr: &mut [T], lo: usize, hi: usize, threads: usize, cutoff: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    if threads <= 1 || hi - lo <= cutoff.max(1) {
+        quick_sort_in_place_traced(arr, lo, hi, tracer);
+        return;
+    }
+
+    let (lt, gt) = partition_traced(arr, lo, hi, tracer);
+
+    parallel_quick_sort_traced(arr, lo, lt, threads / 2, cutoff, tracer);
+    parallel_quick_sort_traced(arr, gt, hi, threads - threads / 2, cutoff, tracer);
+}
+
+/// Runs [`parallel_quick_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison, swap and partition
+///
+/// The partitions are the ones `config` leads to, but the sort runs on a single thread, so the two
+/// sides of every partition are reported one after the other.
+pub fn parallel_quick_sort_steps<T, C, R>(arr: &[T], config: &ParallelConfig, consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    let (threads, cutoff) = (config.threads, config.sequential_cutoff);
+
+    return trace(arr, consume, move |arr, tracer| parallel_quick_sort_traced(arr, 0, arr.len(), threads, cutoff, tracer));
+}
+
+/// Runs [`parallel_radix_sort`] on a copy of `arr` and lends `consume` its trace, recording the
+/// writes of every counting pass
+///
+/// Only the histograms are counted in parallel, which moves nothing, so the trace is the same as
+/// that of [`lsd_radix_sort_steps`](crate::radix_sort::lsd_radix_sort_steps) whatever the config.
+pub fn parallel_radix_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: RadixKey + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| lsd_radix_sort_traced(arr, tracer));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merge_sort::merge_sort_steps;
+    use crate::quick_sort::quick_sort_in_place_steps;
+    use crate::radix_sort::lsd_radix_sort_steps;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{is_stably_sorted, tag};
+
+    fn random(count: usize, modulo: u64) -> Vec<u64> {
+        let mut state: u64 = 0x9e3779b97f4a7c15;
+
+        return (0..count)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+
+                return state % modulo;
+            })
+            .collect();
+    }
+
+    fn configs() -> Vec<ParallelConfig> {
+        return Vec::from([
+            ParallelConfig::new(1, 16),
+            ParallelConfig::new(2, 16),
+            ParallelConfig::new(3, 1),
+            ParallelConfig::new(8, 64),
+            ParallelConfig::default(),
+        ]);
+    }
+
+    #[test]
+    fn test_parallel_merge_sort() {
+        let nums = random(5000, 1000);
+        let mut expected = nums.clone();
+
+        expected.sort();
+
+        for config in configs() {
+            assert_eq!(parallel_merge_sort(&nums, &config), expected);
+            assert_eq!(parallel_merge_sort(&nums[..1], &config), nums[..1]);
+        }
+
+        assert_eq!(parallel_merge_sort::<u64>(&[], &ParallelConfig::new(4, 0)), []);
+
+        let tagged = tag(&random(5000, 16));
+
+        for config in configs() {
+            assert!(is_stably_sorted(&parallel_merge_sort(&tagged, &config)));
+        }
+    }
+
+    #[test]
+    fn test_parallel_quick_sort() {
+        let nums = random(5000, 100);
+        let mut expected = nums.clone();
+
+        expected.sort();
+
+        for config in configs() {
+            let mut sorted = nums.clone();
+            let mut equal = Vec::from([7; 1000]);
+
+            parallel_quick_sort(&mut sorted, &config);
+            parallel_quick_sort(&mut equal, &config);
+
+            assert_eq!(sorted, expected);
+            assert_eq!(equal, [7; 1000]);
+        }
+    }
+
+    #[test]
+    fn test_parallel_radix_sort() {
+        let nums = random(5000, u64::MAX);
+        let signed: Vec<i32> = random(5000, u64::MAX).into_iter().map(|n| n as i32).collect();
+        let mut expected = nums.clone();
+        let mut expected_signed = signed.clone();
+
+        expected.sort();
+        expected_signed.sort();
+
+        for config in configs() {
+            let mut sorted = nums.clone();
+            let mut sorted_signed = signed.clone();
+
+            parallel_radix_sort(&mut sorted, &config);
+            parallel_radix_sort(&mut sorted_signed, &config);
+
+            assert_eq!(sorted, expected);
+            assert_eq!(sorted_signed, expected_signed);
+        }
+    }
+
+    #[test]
+    fn test_parallel_merge_sort_steps() {
+        let tagged = tag(&random(1000, 16));
+
+        for config in configs() {
+            let mut replayed = tagged.clone();
+            let (traced, events) = parallel_merge_sort_steps(&tagged, &config, collect_trace);
+
+            assert_eq!(traced, parallel_merge_sort(&tagged, &config));
+
+            replay(&mut replayed, events);
+            assert!(is_stably_sorted(&replayed));
+        }
+
+        // A single thread sorts the whole array sequentially
+        let nums = random(300, 50);
+
+        assert_eq!(
+            parallel_merge_sort_steps(&nums, &ParallelConfig::new(1, 16), collect_trace),
+            merge_sort_steps(&nums, collect_trace)
+        );
+    }
+
+    #[test]
+    fn test_parallel_quick_sort_steps() {
+        let nums = random(1000, 100);
+
+        for config in configs() {
+            let mut sorted = nums.clone();
+            let mut replayed = nums.clone();
+            let (traced, events) = parallel_quick_sort_steps(&nums, &config, collect_trace);
+
+            parallel_quick_sort(&mut sorted, &config);
+
+            assert_eq!(traced, sorted);
+
+            replay(&mut replayed, events);
+            assert_eq!(replayed, sorted);
+        }
+
+        assert_eq!(
+            parallel_quick_sort_steps(&nums, &ParallelConfig::new(1, 16), collect_trace),
+            quick_sort_in_place_steps(&nums, collect_trace)
+        );
+    }
+
+    #[test]
+    fn test_parallel_radix_sort_steps() {
+        let nums = random(1000, u64::MAX);
+        let mut sorted = nums.clone();
+        let mut replayed = nums.clone();
+        let (traced, events) = parallel_radix_sort_steps(&nums, collect_trace);
+
+        parallel_radix_sort(&mut sorted, &ParallelConfig::new(4, 16));
+
+        assert_eq!(traced, sorted);
+        assert_eq!((traced, events.clone()), lsd_radix_sort_steps(&nums, collect_trace));
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, sorted);
+    }
+}
@@ -1,24 +1,33 @@
+use crate::binary_heap::{heap_sort, heap_sort_traced};
+use crate::insertion_sort::insertion_sort;
+use crate::sort_stats::SortStats;
+use crate::sort_trace::{trace, SortEvent, SortTrace, Tracer};
+
+/// Out-of-place quick sort that returns a sorted copy of `arr`, using the last element as pivot
+///
+/// The sort is not stable: elements equal to the pivot that come before it end up after it.
+/// Use [`stable_quick_sort`] when equal elements must keep their order.
 pub fn quick_sort<T>(arr: &[T]) -> Vec<T>
 where
-    T: PartialOrd + Copy,
+    T: PartialOrd + Clone,
 {
     if arr.len() <= 1 {
         return arr.to_vec();
     }
 
-    let pivot = arr[arr.len() - 1];
+    let pivot = arr[arr.len() - 1].clone();
     let mut left = Vec::new();
     let mut right = Vec::new();
 
-    for i in 0..arr.len() - 1 {
-        if arr[i] < pivot {
-            left.push(arr[i]);
+    for item in &arr[..arr.len() - 1] {
+        if *item < pivot {
+            left.push(item.clone());
         } else {
-            right.push(arr[i]);
+            right.push(item.clone());
         }
     }
 
-    let mut result = Vec::from(quick_sort(&left));
+    let mut result = quick_sort(&left);
 
     result.push(pivot);
     result.extend(quick_sort(&right));
@@ -26,9 +35,478 @@ where
     return result;
 }
 
+/// Out-of-place quick sort that keeps equal elements in their original order
+///
+/// Each partition copies the elements less than, equal to and greater than a median-of-three
+/// pivot into three buffers in the order they appear, so the sort is stable and runs of equal
+/// keys are never recursed into.
+///
+/// # Example
+/// ```
+/// let nums = Vec::from([4, 1, 3, 1, 2]);
+///
+/// assert_eq!(algorithms_exercises::quick_sort::stable_quick_sort(&nums), [1, 1, 2, 3, 4]);
+/// ```
+pub fn stable_quick_sort<T>(arr: &[T]) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+{
+    if arr.len() <= 1 {
+        return arr.to_vec();
+    }
+
+    let pivot = arr[median_of_three(arr)].clone();
+    let mut less = Vec::new();
+    let mut equal = Vec::new();
+    let mut greater = Vec::new();
+
+    for item in arr {
+        if *item < pivot {
+            less.push(item.clone());
+        } else if *item > pivot {
+            greater.push(item.clone());
+        } else {
+            equal.push(item.clone());
+        }
+    }
+
+    let mut result = stable_quick_sort(&less);
+
+    result.extend(equal);
+    result.extend(stable_quick_sort(&greater));
+
+    return result;
+}
+
+/// Index of the median of the first, middle and last elements, used as the pivot
+pub(crate) fn median_of_three<T: PartialOrd>(arr: &[T]) -> usize {
+    let (a, b, c) = (0, arr.len() / 2, arr.len() - 1);
+
+    if (arr[a] < arr[b]) == (arr[b] < arr[c]) {
+        return b;
+    } else if (arr[b] < arr[a]) == (arr[a] < arr[c]) {
+        return a;
+    }
+
+    return c;
+}
+
+/// Three-way partition of `arr` around the value at index `pivot`
+///
+/// Returns `(lt, gt)` such that `arr[..lt]` is less than the pivot, `arr[lt..gt]` is equal to it
+/// and `arr[gt..]` is greater, so runs of equal keys don't degrade the sort to quadratic time.
+/// An empty `arr` has nothing to partition and gives `(0, 0)`.
+///
+/// # Panics
+/// When `arr` is not empty and `pivot >= arr.len()`.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([4, 1, 4, 9, 0, 4]);
+/// let (lt, gt) = algorithms_exercises::quick_sort::partition(&mut nums, 0);
+///
+/// assert_eq!((lt, gt), (2, 5));
+/// assert!(nums[..lt].iter().all(|&n| n < 4));
+/// assert_eq!(nums[lt..gt], [4, 4, 4]);
+/// assert_eq!(nums[gt..], [9]);
+/// ```
+pub fn partition<T: PartialOrd>(arr: &mut [T], pivot: usize) -> (usize, usize) {
+    if arr.is_empty() {
+        return (0, 0);
+    }
+
+    arr.swap(0, pivot);
+
+    // arr[lt] always holds a pivot-equal element, arr[lt..i] are equal and arr[gt..] are greater
+    let mut lt = 0;
+    let mut i = 1;
+    let mut gt = arr.len();
+
+    while i < gt {
+        if arr[i] < arr[lt] {
+            arr.swap(lt, i);
+            lt += 1;
+            i += 1;
+        } else if arr[i] > arr[lt] {
+            gt -= 1;
+            arr.swap(i, gt);
+        } else {
+            i += 1;
+        }
+    }
+
+    return (lt, gt);
+}
+
+/// In-place quick sort with median-of-three pivots and three-way partitioning
+///
+/// Recurses into the smaller side and loops over the larger one, so the stack depth stays
+/// logarithmic. The sort is not stable, see [`stable_quick_sort`].
+pub fn quick_sort_in_place<T: PartialOrd>(arr: &mut [T]) {
+    let mut arr = arr;
+
+    while arr.len() > 1 {
+        let (lt, gt) = partition(arr, median_of_three(arr));
+        let (left, rest) = arr.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+
+        if left.len() < right.len() {
+            quick_sort_in_place(left);
+            arr = right;
+        } else {
+            quick_sort_in_place(right);
+            arr = left;
+        }
+    }
+}
+
+/// Slices of at most this many elements are finished by [`intro_sort`] with insertion sort
+const INTRO_SORT_INSERTION_CUTOFF: usize = 16;
+
+fn intro_sort_with_limit<T: PartialOrd>(arr: &mut [T], mut limit: usize) {
+    let mut arr = arr;
+
+    while arr.len() > INTRO_SORT_INSERTION_CUTOFF {
+        if limit == 0 {
+            heap_sort(arr);
+            return;
+        }
+
+        limit -= 1;
+
+        let (lt, gt) = partition(arr, median_of_three(arr));
+        let (left, rest) = arr.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+
+        if left.len() < right.len() {
+            intro_sort_with_limit(left, limit);
+            arr = right;
+        } else {
+            intro_sort_with_limit(right, limit);
+            arr = left;
+        }
+    }
+
+    insertion_sort(arr);
+}
+
+/// [`quick_sort_in_place`] that switches to [`heap_sort`] once it has partitioned `2·log2(n)`
+/// levels deep, so the worst case is O(n log n)
+///
+/// Slices of up to 16 elements are finished with insertion sort. The sort is not stable.
+///
+/// # Example
+/// ```
+/// let mut nums: Vec<u32> = (0..100).rev().collect();
+///
+/// algorithms_exercises::quick_sort::intro_sort(&mut nums);
+/// assert_eq!(nums, (0..100).collect::<Vec<_>>());
+/// ```
+pub fn intro_sort<T: PartialOrd>(arr: &mut [T]) {
+    let limit = 2 * (usize::BITS - arr.len().leading_zeros()) as usize;
+
+    intro_sort_with_limit(arr, limit);
+}
+
+fn quick_sort_with_depth<T>(arr: &[T], depth: usize, stats: &mut SortStats) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+{
+    stats.enter(depth);
+
+    if arr.len() <= 1 {
+        stats.allocate::<T>(arr.len());
+        stats.moves += arr.len();
+
+        return arr.to_vec();
+    }
+
+    let pivot = arr[arr.len() - 1].clone();
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for item in &arr[..arr.len() - 1] {
+        if stats.less(item, &pivot) {
+            left.push(item.clone());
+        } else {
+            right.push(item.clone());
+        }
+    }
+
+    stats.allocate::<T>(left.capacity() + right.capacity());
+    stats.moves += arr.len() - 1;
+
+    let mut result = quick_sort_with_depth(&left, depth + 1, stats);
+    let capacity = result.capacity();
+
+    result.push(pivot);
+    result.extend(quick_sort_with_depth(&right, depth + 1, stats));
+
+    // Growing `result` reallocates it, the recursive call already counted the original buffer
+    stats.allocate::<T>(result.capacity() - capacity);
+    stats.moves += right.len() + 1;
+
+    return result;
+}
+
+/// [`quick_sort`] that records its comparisons, element copies, recursion depth and
+/// allocations in `stats`
+///
+/// `bytes_allocated` counts the final capacity of every partition buffer, not each of the
+/// intermediate reallocations made while it grows.
+pub fn quick_sort_with_stats<T>(arr: &[T], stats: &mut SortStats) -> Vec<T>
+where
+    T: PartialOrd + Clone,
+{
+    return quick_sort_with_depth(arr, 1, stats);
+}
+
+fn quick_sort_traced<T>(arr: &mut [T], lo: usize, hi: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    if hi - lo <= 1 {
+        return;
+    }
+
+    let pivot = arr[hi - 1].clone();
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for (offset, item) in arr[lo..hi - 1].iter().enumerate() {
+        tracer.compare(lo + offset, hi - 1);
+
+        if *item < pivot {
+            left.push(item.clone());
+        } else {
+            right.push(item.clone());
+        }
+    }
+
+    let pivot_index = lo + left.len();
+
+    for (k, value) in left.into_iter().enumerate() {
+        tracer.write(arr, lo + k, value);
+    }
+
+    tracer.write(arr, pivot_index, pivot);
+
+    for (k, value) in right.into_iter().enumerate() {
+        tracer.write(arr, pivot_index + 1 + k, value);
+    }
+
+    tracer.push(SortEvent::PartitionDone(lo, hi, pivot_index));
+    quick_sort_traced(arr, lo, pivot_index, tracer);
+    quick_sort_traced(arr, pivot_index + 1, hi, tracer);
+}
+
+/// Runs [`quick_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison, write and partition
+///
+/// Each partition writes the smaller elements, the pivot and the rest back into the range it
+/// was taken from.
+pub fn quick_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| quick_sort_traced(arr, 0, arr.len(), tracer));
+}
+
+/// [`median_of_three`] of `arr[lo..hi]`, recording its comparisons
+fn median_of_three_traced<T>(arr: &[T], lo: usize, hi: usize, tracer: &mut Tracer<T>) -> usize
+where
+    T: PartialOrd + Clone,
+{
+    let (a, b, c) = (lo, lo + (hi - lo) / 2, hi - 1);
+
+    tracer.compare(a, b);
+    tracer.compare(b, c);
+
+    if (arr[a] < arr[b]) == (arr[b] < arr[c]) {
+        return b;
+    }
+
+    tracer.compare(b, a);
+    tracer.compare(a, c);
+
+    return if (arr[b] < arr[a]) == (arr[a] < arr[c]) { a } else { c };
+}
+
+/// [`partition`] of `arr[lo..hi]` around its median of three, recording every comparison and
+/// swap, returns `(lt, gt)` as indices into `arr`
+pub(crate) fn partition_traced<T>(arr: &mut [T], lo: usize, hi: usize, tracer: &mut Tracer<T>) -> (usize, usize)
+where
+    T: PartialOrd + Clone,
+{
+    let pivot = median_of_three_traced(arr, lo, hi, tracer);
+
+    tracer.swap(arr, lo, pivot);
+
+    let mut lt = lo;
+    let mut i = lo + 1;
+    let mut gt = hi;
+
+    while i < gt {
+        tracer.compare(i, lt);
+
+        if arr[i] < arr[lt] {
+            tracer.swap(arr, lt, i);
+            lt += 1;
+            i += 1;
+        } else if arr[i] > arr[lt] {
+            gt -= 1;
+            tracer.swap(arr, i, gt);
+        } else {
+            i += 1;
+        }
+    }
+
+    tracer.push(SortEvent::PartitionDone(lo, hi, lt));
+
+    return (lt, gt);
+}
+
+pub(crate) fn quick_sort_in_place_traced<T>(arr: &mut [T], lo: usize, hi: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    if hi - lo <= 1 {
+        return;
+    }
+
+    let (lt, gt) = partition_traced(arr, lo, hi, tracer);
+
+    quick_sort_in_place_traced(arr, lo, lt, tracer);
+    quick_sort_in_place_traced(arr, gt, hi, tracer);
+}
+
+/// Runs [`quick_sort_in_place`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison, swap and partition
+///
+/// `PartitionDone` reports the first index of the run of pivot-equal elements.
+pub fn quick_sort_in_place_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| quick_sort_in_place_traced(arr, 0, arr.len(), tracer));
+}
+
+fn stable_quick_sort_traced<T>(arr: &mut [T], lo: usize, hi: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    if hi - lo <= 1 {
+        return;
+    }
+
+    let pivot_index = median_of_three_traced(arr, lo, hi, tracer);
+    let pivot = arr[pivot_index].clone();
+    let mut less = Vec::new();
+    let mut equal = Vec::new();
+    let mut greater = Vec::new();
+
+    for (offset, item) in arr[lo..hi].iter().enumerate() {
+        tracer.compare(lo + offset, pivot_index);
+
+        if *item < pivot {
+            less.push(item.clone());
+        } else if *item > pivot {
+            greater.push(item.clone());
+        } else {
+            equal.push(item.clone());
+        }
+    }
+
+    let lt = lo + less.len();
+    let gt = lt + equal.len();
+
+    for (k, value) in less.into_iter().chain(equal).chain(greater).enumerate() {
+        tracer.write(arr, lo + k, value);
+    }
+
+    tracer.push(SortEvent::PartitionDone(lo, hi, lt));
+    stable_quick_sort_traced(arr, lo, lt, tracer);
+    stable_quick_sort_traced(arr, gt, hi, tracer);
+}
+
+/// Runs [`stable_quick_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison, write and partition
+///
+/// Each partition compares every element with the pivot at the index it had when the partition
+/// started, then writes the smaller, equal and greater elements back into the range.
+/// `PartitionDone` reports the first index of the run of pivot-equal elements.
+pub fn stable_quick_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| stable_quick_sort_traced(arr, 0, arr.len(), tracer));
+}
+
+fn intro_sort_traced<T>(arr: &mut [T], mut lo: usize, mut hi: usize, mut limit: usize, tracer: &mut Tracer<T>)
+where
+    T: PartialOrd + Clone,
+{
+    while hi - lo > INTRO_SORT_INSERTION_CUTOFF {
+        if limit == 0 {
+            heap_sort_traced(arr, lo, hi, tracer);
+            return;
+        }
+
+        limit -= 1;
+
+        let (lt, gt) = partition_traced(arr, lo, hi, tracer);
+
+        if lt - lo < hi - gt {
+            intro_sort_traced(arr, lo, lt, limit, tracer);
+            lo = gt;
+        } else {
+            intro_sort_traced(arr, gt, hi, limit, tracer);
+            hi = lt;
+        }
+    }
+
+    for index in lo + 1..hi {
+        let mut j = index;
+
+        while j > lo {
+            tracer.compare(j - 1, j);
+
+            if arr[j - 1] <= arr[j] {
+                break;
+            }
+
+            tracer.swap(arr, j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+/// Runs [`intro_sort`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison, swap and partition
+///
+/// The heap sort and insertion sort that finish small or deep slices are reported as their
+/// comparisons and swaps, `PartitionDone` reports the first index of the run of pivot-equal
+/// elements.
+pub fn intro_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let limit = 2 * (usize::BITS - arr.len().leading_zeros()) as usize;
+
+        intro_sort_traced(arr, 0, arr.len(), limit, tracer);
+    });
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{generate, is_stably_sorted, tag, Distribution};
 
     #[test]
     fn test_quick_sort() {
@@ -39,4 +517,183 @@ mod tests {
             [1, 2, 3, 4, 5, 6, 6, 7]
         );
     }
+
+    #[test]
+    fn test_stable_quick_sort() {
+        assert_eq!(stable_quick_sort::<u8>(&[]), []);
+        assert_eq!(stable_quick_sort(&Vec::from([3, 2, 1])), [1, 2, 3]);
+        assert_eq!(stable_quick_sort(&Vec::from([1, 5, 7, 4, 6, 2, 3, 6])), [1, 2, 3, 4, 5, 6, 6, 7]);
+
+        for distribution in Distribution::ALL {
+            let keys: Vec<u64> = generate(distribution, 500, 2).iter().map(|key| key % 8).collect();
+
+            assert!(is_stably_sorted(&stable_quick_sort(&tag(&keys))), "{}", distribution);
+        }
+
+        assert!(!is_stably_sorted(&quick_sort(&tag(&[1, 1, 0]))));
+    }
+
+    #[test]
+    fn test_partition() {
+        let mut nums = Vec::from([5, 2, 8, 5, 1, 9, 5]);
+        let (lt, gt) = partition(&mut nums, 3);
+
+        assert_eq!((lt, gt), (2, 5));
+        assert!(nums[..lt].iter().all(|&n| n < 5));
+        assert_eq!(nums[lt..gt], [5, 5, 5]);
+        assert!(nums[gt..].iter().all(|&n| n > 5));
+
+        let mut single = Vec::from([1]);
+
+        assert_eq!(partition(&mut single, 0), (0, 1));
+        assert_eq!(partition::<u8>(&mut [], 0), (0, 0));
+    }
+
+    #[test]
+    fn test_quick_sort_in_place() {
+        let mut empty: Vec<usize> = Vec::new();
+        let mut nums = Vec::from([1, 5, 7, 4, 6, 2, 3, 6]);
+        let mut sorted: Vec<usize> = (0..10_000).collect();
+        let mut equal = Vec::from([3; 10_000]);
+
+        quick_sort_in_place(&mut empty);
+        quick_sort_in_place(&mut nums);
+        quick_sort_in_place(&mut sorted);
+        quick_sort_in_place(&mut equal);
+
+        assert_eq!(empty, Vec::new());
+        assert_eq!(nums, [1, 2, 3, 4, 5, 6, 6, 7]);
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(equal, [3; 10_000]);
+    }
+
+    #[test]
+    fn test_intro_sort() {
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 1000, 10);
+            let mut expected = nums.clone();
+
+            expected.sort();
+
+            // A limit of 0 sorts everything above the insertion sort cutoff with heap sort
+            for limit in [0, 3, 20] {
+                let mut sorted = nums.clone();
+
+                intro_sort_with_limit(&mut sorted, limit);
+                assert_eq!(sorted, expected, "{} limit={}", distribution, limit);
+            }
+
+            let mut sorted = nums.clone();
+
+            intro_sort(&mut sorted);
+            assert_eq!(sorted, expected, "{}", distribution);
+        }
+
+        intro_sort::<u8>(&mut []);
+    }
+
+    #[test]
+    fn test_quick_sort_with_stats() {
+        let mut stats = SortStats::new();
+        let sorted: Vec<u32> = (0..8).collect();
+
+        assert_eq!(quick_sort_with_stats(&[1, 5, 7, 4, 6, 2, 3, 6], &mut stats), [1, 2, 3, 4, 5, 6, 6, 7]);
+        assert!(stats.comparisons > 0 && stats.moves > 0 && stats.bytes_allocated > 0);
+
+        let mut stats = SortStats::new();
+
+        // Sorted input is the worst case for a last-element pivot: n - 1 levels of partitions
+        assert_eq!(quick_sort_with_stats(&sorted, &mut stats), sorted);
+        assert_eq!(stats.comparisons, 7 + 6 + 5 + 4 + 3 + 2 + 1);
+        assert_eq!(stats.max_depth, 8);
+        assert_eq!(stats.swaps, 0);
+    }
+
+    #[test]
+    fn test_quick_sort_steps() {
+        let (traced, events) = quick_sort_steps(&[3, 1, 2], collect_trace);
+
+        assert_eq!(traced, [1, 2, 3]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Compare(0, 2),
+                SortEvent::Compare(1, 2),
+                SortEvent::Write(0, 1),
+                SortEvent::Write(1, 2),
+                SortEvent::Write(2, 3),
+                SortEvent::PartitionDone(0, 3, 1),
+            ]
+        );
+
+        let nums = Vec::from([1, 5, 7, 4, 6, 2, 3, 6]);
+        let mut replayed = nums.clone();
+
+        quick_sort_steps(&nums, |trace| replay(&mut replayed, trace));
+        assert_eq!(replayed, quick_sort(&nums));
+    }
+
+    #[test]
+    fn test_quick_sort_in_place_steps() {
+        let nums = Vec::from([1, 5, 7, 4, 6, 2, 3, 6, 6, 0]);
+        let (traced, events) = quick_sort_in_place_steps(&nums, collect_trace);
+        let mut sorted = nums.clone();
+        let mut replayed = nums.clone();
+
+        quick_sort_in_place(&mut sorted);
+
+        assert_eq!(traced, sorted);
+        assert!(events.iter().any(|event| matches!(event, SortEvent::PartitionDone(0, 10, _))));
+        assert!(events.iter().all(|event| !matches!(event, SortEvent::Write(..))));
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, sorted);
+    }
+
+    #[test]
+    fn test_stable_quick_sort_steps() {
+        let keys = generate(Distribution::FewUnique, 300, 5);
+        let (traced, events) = stable_quick_sort_steps(&tag(&keys), collect_trace);
+        let mut replayed = tag(&keys);
+        let sorted = traced;
+
+        assert!(is_stably_sorted(&sorted));
+        assert!(events.iter().any(|event| matches!(event, SortEvent::PartitionDone(0, 300, _))));
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, sorted);
+    }
+
+    #[test]
+    fn test_intro_sort_steps() {
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 500, 21);
+            let (traced, events) = intro_sort_steps(&nums, collect_trace);
+            let mut sorted = nums.clone();
+            let mut replayed = nums.clone();
+
+            intro_sort(&mut sorted);
+
+            assert_eq!(traced, sorted, "{}", distribution);
+
+            replay(&mut replayed, events);
+            assert_eq!(replayed, sorted, "{}", distribution);
+        }
+
+        // Without a partition budget the whole array goes to the heap sort
+        let nums = generate(Distribution::Random, 500, 22);
+        let (sorted, events) = trace(&nums, collect_trace, |arr, tracer| intro_sort_traced(arr, 0, arr.len(), 0, tracer));
+        let mut replayed = nums.clone();
+
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        assert!(events.iter().all(|event| !matches!(event, SortEvent::PartitionDone(..))));
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, sorted);
+
+        // Only the insertion sort runs on slices this short
+        let events = intro_sort_steps(&[2, 1], |trace| trace.collect::<Vec<_>>());
+
+        assert_eq!(events, [SortEvent::Compare(0, 1), SortEvent::Swap(1, 0)]);
+    }
 }
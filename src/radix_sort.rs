@@ -1,60 +1,766 @@
-fn nth_digit(number: usize, position: usize) -> u8 {
-    return ((number / position as usize) % 10) as u8;
+use crate::insertion_sort::insertion_sort_by;
+use crate::sort_trace::{trace, SortTrace, Tracer};
+
+/// Key that can be sorted one byte at a time
+///
+/// `radix_byte(0)` is the least significant byte of an unsigned value whose order matches the
+/// order of the key, so signed integers have their sign bit flipped.
+///
+/// Floats are mapped with the IEEE-754 order-preserving transform: positive values get their sign
+/// bit set and negative values have all of their bits flipped. This gives the same total order as
+/// `f64::total_cmp`:
+///
+/// `-NaN < -inf < ... < -0.0 < +0.0 < ... < +inf < +NaN`
+///
+/// so `-0.0` sorts before `0.0`, and NaNs are grouped at either end depending on their sign bit
+/// (ordered by payload among themselves).
+pub trait RadixKey: Copy {
+    /// Number of bytes (and therefore of counting passes) in the key
+    const BYTES: usize;
+
+    fn radix_byte(&self, n: usize) -> u8;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn radix_byte(&self, n: usize) -> u8 {
+                    return (*self >> (n * 8)) as u8;
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_radix_key_signed {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn radix_byte(&self, n: usize) -> u8 {
+                    let flipped = (*self as $u) ^ (1 << (<$u>::BITS - 1));
+
+                    return (flipped >> (n * 8)) as u8;
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_radix_key_float {
+    ($($t:ty => $u:ty),*) => {
+        $(
+            impl RadixKey for $t {
+                const BYTES: usize = std::mem::size_of::<$t>();
+
+                fn radix_byte(&self, n: usize) -> u8 {
+                    let bits = self.to_bits();
+                    let sign = 1 << (<$u>::BITS - 1);
+                    let ordered = if bits & sign == 0 { bits | sign } else { !bits };
+
+                    return (ordered >> (n * 8)) as u8;
+                }
+            }
+        )*
+    };
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, u128, usize);
+impl_radix_key_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+impl_radix_key_float!(f32 => u32, f64 => u64);
+
+/// Histograms of every byte position, `counts[n][b]` is the number of keys whose `n`th byte is `b`
+pub(crate) fn count_bytes<T: RadixKey>(arr: &[T]) -> Vec<[usize; 256]> {
+    let mut counts = vec![[0; 256]; T::BYTES];
+
+    for item in arr {
+        for (n, count) in counts.iter_mut().enumerate() {
+            count[item.radix_byte(n) as usize] += 1;
+        }
+    }
+
+    return counts;
+}
+
+/// In-place LSD radix sort
+///
+/// Runs one stable counting pass per byte of the key with 256 buckets, ping-ponging between the
+/// input and a single scratch buffer. All histograms are built in one read of the input, and a
+/// pass is skipped when every key has the same byte at that position (e.g. the high bytes of
+/// small numbers). The sort is stable.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([3i32, -1, 200, 0, -50]);
+///
+/// algorithms_exercises::radix_sort::lsd_radix_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([-50, -1, 0, 3, 200]));
+/// ```
+pub fn lsd_radix_sort<T: RadixKey>(arr: &mut [T]) {
+    if arr.len() <= 1 {
+        return;
+    }
+
+    let counts = count_bytes(arr);
+
+    lsd_radix_sort_with_counts(arr, &counts);
+}
+
+/// The counting passes of [`lsd_radix_sort`], given the histograms from [`count_bytes`]
+pub(crate) fn lsd_radix_sort_with_counts<T: RadixKey>(arr: &mut [T], counts: &[[usize; 256]]) {
+    let mut scratch = arr.to_vec();
+    let mut in_scratch = false;
+
+    for (n, count) in counts.iter().enumerate() {
+        if count.contains(&arr.len()) {
+            continue;
+        }
+
+        let mut offsets = [0; 256];
+        let mut total = 0;
+
+        for (offset, c) in offsets.iter_mut().zip(count) {
+            *offset = total;
+            total += c;
+        }
+
+        let (src, dst) = if in_scratch {
+            (&scratch[..], &mut arr[..])
+        } else {
+            (&arr[..], &mut scratch[..])
+        };
+
+        for item in src {
+            let byte = item.radix_byte(n) as usize;
+
+            dst[offsets[byte]] = *item;
+            offsets[byte] += 1;
+        }
+
+        in_scratch = !in_scratch;
+    }
+
+    if in_scratch {
+        arr.copy_from_slice(&scratch);
+    }
+}
+
+/// [`lsd_radix_sort`] that reports each counting pass as the writes of its output
+pub(crate) fn lsd_radix_sort_traced<T: RadixKey>(arr: &mut [T], tracer: &mut Tracer<T>) {
+    let counts = count_bytes(arr);
+
+    for (n, count) in counts.iter().enumerate() {
+        if arr.len() <= 1 || count.contains(&arr.len()) {
+            continue;
+        }
+
+        let mut offsets = [0; 256];
+        let mut total = 0;
+
+        for (offset, c) in offsets.iter_mut().zip(count) {
+            *offset = total;
+            total += c;
+        }
+
+        let mut pass = arr.to_vec();
+
+        for item in arr.iter() {
+            let byte = item.radix_byte(n) as usize;
+
+            pass[offsets[byte]] = *item;
+            offsets[byte] += 1;
+        }
+
+        for (i, item) in pass.into_iter().enumerate() {
+            tracer.write(arr, i, item);
+        }
+    }
 }
 
-fn get_longest_number_digits(arr: &[usize]) -> usize {
-    return arr
-        .iter()
-        .map(|num| num.to_string().len())
-        .max()
-        .unwrap_or(0);
+/// Runs [`lsd_radix_sort`] on a copy of `arr` and lends `consume` its trace, recording the writes
+/// of every counting pass
+///
+/// Each pass is reported as the writes that put its output back into the array, skipped passes
+/// produce no events.
+pub fn lsd_radix_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: RadixKey + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| lsd_radix_sort_traced(arr, tracer));
 }
 
-pub fn radix_sort(arr: &[usize]) -> Vec<usize> {
-    let mut position = 1;
+/// Returns a sorted copy of `arr`, see [`lsd_radix_sort`]
+///
+/// The sort is stable.
+pub fn radix_sort<T: RadixKey>(arr: &[T]) -> Vec<T> {
     let mut result = arr.to_vec();
-    let mut longest_number_digits = get_longest_number_digits(&arr);
-    let mut buckets: Vec<Vec<usize>> = vec![Vec::with_capacity(arr.len()); 10];
 
-    while longest_number_digits > 0 {
-        while !result.is_empty() {
-            let number = result.remove(0);
-            let digit = nth_digit(number, position);
+    lsd_radix_sort(&mut result);
+
+    return result;
+}
+
+/// Buckets at or below this size are finished with insertion sort instead of another MSD level
+const MSD_INSERTION_CUTOFF: usize = 16;
+
+/// Bucket of the `depth`th byte, where bucket 0 holds keys that are shorter than `depth + 1`
+fn byte_bucket(key: &[u8], depth: usize) -> usize {
+    return key.get(depth).map_or(0, |&byte| byte as usize + 1);
+}
+
+fn bytes_of<T: AsRef<[u8]>>(item: &T) -> &[u8] {
+    return item.as_ref();
+}
+
+fn insertion_sort_suffixes<T, F>(arr: &mut [T], depth: usize, key: &F)
+where
+    F: Fn(&T) -> &[u8],
+{
+    insertion_sort_by(arr, |a, b| {
+        let a = key(a).get(depth..).unwrap_or(&[]);
+        let b = key(b).get(depth..).unwrap_or(&[]);
+
+        return a.cmp(b);
+    });
+}
+
+/// Moves `arr[i]` to `arr[dest[i]]` for every `i` by following the permutation's cycles
+pub(crate) fn apply_permutation<T>(arr: &mut [T], dest: &mut [usize]) {
+    for i in 0..arr.len() {
+        while dest[i] != i {
+            let d = dest[i];
+
+            arr.swap(i, d);
+            dest.swap(i, d);
+        }
+    }
+}
+
+/// Start of every bucket of the `depth`th byte in `arr`, with `starts[257]` the end of the last
+fn bucket_starts<T, F>(arr: &[T], depth: usize, key: &F) -> [usize; 258]
+where
+    F: Fn(&T) -> &[u8],
+{
+    let mut starts = [0; 258];
+
+    for item in arr.iter() {
+        starts[byte_bucket(key(item), depth) + 1] += 1;
+    }
+
+    for bucket in 1..258 {
+        starts[bucket] += starts[bucket - 1];
+    }
+
+    return starts;
+}
+
+/// Pushes the buckets that still need sorting by the next byte onto `stack`
+///
+/// Bucket 0 holds keys that ended at this depth, they are all equal.
+fn push_buckets(stack: &mut Vec<(usize, usize, usize)>, offset: usize, starts: &[usize; 258], depth: usize) {
+    for bucket in 1..257 {
+        if starts[bucket + 1] - starts[bucket] > 1 {
+            stack.push((offset + starts[bucket], offset + starts[bucket + 1], depth + 1));
+        }
+    }
+}
+
+/// Sorts `arr` bucket by bucket, keeping the buckets left to sort on an explicit stack of
+/// `(start, end, depth)` ranges rather than recursing, as keys sharing a long prefix take one
+/// level per shared byte
+fn msd_sort<T, F>(arr: &mut [T], key: &F)
+where
+    F: Fn(&T) -> &[u8],
+{
+    let mut stack = Vec::from([(0, arr.len(), 0)]);
 
-            buckets[digit as usize].push(number);
+    while let Some((start, end, depth)) = stack.pop() {
+        let arr = &mut arr[start..end];
+
+        if arr.len() <= MSD_INSERTION_CUTOFF {
+            insertion_sort_suffixes(arr, depth, key);
+            continue;
         }
 
-        for bucket in &mut buckets {
-            while !bucket.is_empty() {
-                let number = bucket.remove(0);
+        let starts = bucket_starts(arr, depth, key);
+        let mut next = starts;
+        let mut dest: Vec<usize> = arr
+            .iter()
+            .map(|item| {
+                let bucket = byte_bucket(key(item), depth);
+
+                next[bucket] += 1;
+
+                return next[bucket] - 1;
+            })
+            .collect();
+
+        apply_permutation(arr, &mut dest);
+        push_buckets(&mut stack, start, &starts, depth);
+    }
+}
+
+/// Like [`msd_sort`], but permutes the keys into their buckets with swaps
+fn american_flag_sort_by<T, F>(arr: &mut [T], key: &F)
+where
+    F: Fn(&T) -> &[u8],
+{
+    let mut stack = Vec::from([(0, arr.len(), 0)]);
+
+    while let Some((start, end, depth)) = stack.pop() {
+        let arr = &mut arr[start..end];
+
+        if arr.len() <= MSD_INSERTION_CUTOFF {
+            insertion_sort_suffixes(arr, depth, key);
+            continue;
+        }
 
-                result.push(number);
+        let starts = bucket_starts(arr, depth, key);
+        let mut next = starts;
+
+        for bucket in 0..257 {
+            while next[bucket] < starts[bucket + 1] {
+                let target = byte_bucket(key(&arr[next[bucket]]), depth);
+
+                if target == bucket {
+                    next[bucket] += 1;
+                } else {
+                    arr.swap(next[bucket], next[target]);
+                    next[target] += 1;
+                }
             }
         }
 
-        position *= 10;
-        longest_number_digits -= 1;
+        push_buckets(&mut stack, start, &starts, depth);
+    }
+}
+
+/// MSD radix sort for byte strings
+///
+/// Distributes keys into 256 buckets by their first byte (plus one bucket for keys that have
+/// already ended, which sort first), then recurses into each bucket with the next byte. Buckets
+/// of up to 16 keys are finished with insertion sort. The sort is stable, and `String`s end up
+/// in the same order as `slice::sort` would put them.
+///
+/// # Example
+/// ```
+/// let mut words = Vec::from(["banana", "apple", "app", "cherry"]);
+///
+/// algorithms_exercises::radix_sort::msd_radix_sort(&mut words);
+/// assert_eq!(words, Vec::from(["app", "apple", "banana", "cherry"]));
+/// ```
+pub fn msd_radix_sort<T: AsRef<[u8]>>(arr: &mut [T]) {
+    msd_sort(arr, &bytes_of::<T>);
+}
+
+/// In-place MSD radix sort for byte strings
+///
+/// Same bucketing as [`msd_radix_sort`], but keys are permuted into their buckets with swaps
+/// (the "American flag" scheme) instead of a computed permutation, so no memory proportional to
+/// the input is needed. The sort is not stable, use [`msd_radix_sort`] when equal keys must keep
+/// their order.
+pub fn american_flag_sort<T: AsRef<[u8]>>(arr: &mut [T]) {
+    american_flag_sort_by(arr, &bytes_of::<T>);
+}
+
+fn first_bytes<K: AsRef<[u8]>>(pair: &(K, usize)) -> &[u8] {
+    return pair.0.as_ref();
+}
+
+/// Stable MSD radix sort of records by a byte-string key
+///
+/// `key` is called once per record and the keys are kept alongside the records' positions while
+/// sorting, so it may return an owned value.
+///
+/// # Example
+/// ```
+/// let mut people = Vec::from([("bob", 31), ("alice", 25), ("bob", 20)]);
+///
+/// algorithms_exercises::radix_sort::radix_sort_by_key(&mut people, |person| person.0);
+/// assert_eq!(people, Vec::from([("alice", 25), ("bob", 31), ("bob", 20)]));
+/// ```
+pub fn radix_sort_by_key<T, K, F>(arr: &mut [T], mut key: F)
+where
+    K: AsRef<[u8]>,
+    F: FnMut(&T) -> K,
+{
+    let mut keyed: Vec<(K, usize)> = arr.iter().enumerate().map(|(i, item)| (key(item), i)).collect();
+
+    msd_sort(&mut keyed, &first_bytes::<K>);
+
+    let mut dest = vec![0; arr.len()];
+
+    for (position, (_, source)) in keyed.iter().enumerate() {
+        dest[*source] = position;
+    }
+
+    apply_permutation(arr, &mut dest);
+}
+
+fn suffix<T: AsRef<[u8]>>(item: &T, depth: usize) -> &[u8] {
+    return item.as_ref().get(depth..).unwrap_or(&[]);
+}
+
+/// [`insertion_sort_suffixes`] of `arr[lo..hi]`, recording every comparison and swap
+fn insertion_sort_suffixes_traced<T>(arr: &mut [T], lo: usize, hi: usize, depth: usize, tracer: &mut Tracer<T>)
+where
+    T: AsRef<[u8]> + Clone,
+{
+    for index in lo + 1..hi {
+        let mut j = index;
+
+        while j > lo {
+            tracer.compare(j - 1, j);
+
+            if suffix(&arr[j - 1], depth) <= suffix(&arr[j], depth) {
+                break;
+            }
+
+            tracer.swap(arr, j, j - 1);
+            j -= 1;
+        }
     }
+}
+
+/// Runs [`msd_radix_sort`] on a copy of `arr` and lends `consume` its trace, recording the writes
+/// that distribute every bucket and the comparisons and swaps of the insertion sorts that finish
+/// the small ones
+pub fn msd_radix_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: AsRef<[u8]> + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut stack = Vec::from([(0, arr.len(), 0)]);
+
+        while let Some((start, end, depth)) = stack.pop() {
+            if end - start <= MSD_INSERTION_CUTOFF {
+                insertion_sort_suffixes_traced(arr, start, end, depth, tracer);
+                continue;
+            }
+
+            let starts = bucket_starts(&arr[start..end], depth, &bytes_of::<T>);
+            let mut next = starts;
+            let mut pass = arr[start..end].to_vec();
+
+            for item in arr[start..end].iter() {
+                let bucket = byte_bucket(item.as_ref(), depth);
+
+                pass[next[bucket]] = item.clone();
+                next[bucket] += 1;
+            }
+
+            for (k, item) in pass.into_iter().enumerate() {
+                tracer.write(arr, start + k, item);
+            }
+
+            push_buckets(&mut stack, start, &starts, depth);
+        }
+    });
+}
+
+/// Runs [`american_flag_sort`] on a copy of `arr` and lends `consume` its trace, recording the
+/// swaps that move keys into their buckets and the comparisons and swaps of the insertion sorts
+/// that finish the small ones
+pub fn american_flag_sort_steps<T, C, R>(arr: &[T], consume: C) -> R
+where
+    T: AsRef<[u8]> + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, |arr, tracer| {
+        let mut stack = Vec::from([(0, arr.len(), 0)]);
+
+        while let Some((start, end, depth)) = stack.pop() {
+            if end - start <= MSD_INSERTION_CUTOFF {
+                insertion_sort_suffixes_traced(arr, start, end, depth, tracer);
+                continue;
+            }
+
+            let starts = bucket_starts(&arr[start..end], depth, &bytes_of::<T>);
+            let mut next = starts;
 
-    return result.to_vec();
+            for bucket in 0..257 {
+                while next[bucket] < starts[bucket + 1] {
+                    let target = byte_bucket(arr[start + next[bucket]].as_ref(), depth);
+
+                    if target == bucket {
+                        next[bucket] += 1;
+                    } else {
+                        tracer.swap(arr, start + next[bucket], start + next[target]);
+                        next[target] += 1;
+                    }
+                }
+            }
+
+            push_buckets(&mut stack, start, &starts, depth);
+        }
+    });
+}
+
+/// Runs [`radix_sort_by_key`] on a copy of `arr` and lends `consume` its trace, recording the swaps
+/// that move every record into place
+///
+/// The keys are sorted on the side, so the trace only holds the swaps of the final permutation.
+pub fn radix_sort_by_key_steps<T, K, F, C, R>(arr: &[T], mut key: F, consume: C) -> R
+where
+    T: Clone + Send,
+    K: AsRef<[u8]>,
+    F: FnMut(&T) -> K + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, move |arr, tracer| {
+        let mut keyed: Vec<(K, usize)> = arr.iter().enumerate().map(|(i, item)| (key(item), i)).collect();
+
+        msd_sort(&mut keyed, &first_bytes::<K>);
+
+        let mut dest = vec![0; arr.len()];
+
+        for (position, (_, source)) in keyed.iter().enumerate() {
+            dest[*source] = position;
+        }
+
+        for i in 0..arr.len() {
+            while dest[i] != i {
+                let d = dest[i];
+
+                tracer.swap(arr, i, d);
+                dest.swap(i, d);
+            }
+        }
+    });
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::sort_trace::{collect_trace, replay, SortEvent};
+
+    #[test]
+    fn test_radix_byte() {
+        assert_eq!(0x1234u16.radix_byte(0), 0x34);
+        assert_eq!(0x1234u16.radix_byte(1), 0x12);
+        assert_eq!((-1i8).radix_byte(0), 0x7f);
+        assert_eq!(0i8.radix_byte(0), 0x80);
+        assert_eq!(i32::MIN.radix_byte(3), 0x00);
+        assert_eq!(i32::MAX.radix_byte(3), 0xff);
+    }
+
+    #[test]
+    fn test_lsd_radix_sort() {
+        let mut empty: Vec<u32> = Vec::new();
+        let mut bytes = Vec::from([255u8, 0, 17, 17, 3]);
+        let mut wide = Vec::from([u128::MAX, 1 << 100, 0, 42, 1 << 64]);
+        let mut signed = Vec::from([i64::MAX, -3, 0, i64::MIN, 7, -3]);
+        let mut small = Vec::from([i16::MIN, i16::MAX, -1, 1]);
+
+        lsd_radix_sort(&mut empty);
+        lsd_radix_sort(&mut bytes);
+        lsd_radix_sort(&mut wide);
+        lsd_radix_sort(&mut signed);
+        lsd_radix_sort(&mut small);
+
+        assert_eq!(empty, Vec::new());
+        assert_eq!(bytes, [0, 3, 17, 17, 255]);
+        assert_eq!(wide, [0, 42, 1 << 64, 1 << 100, u128::MAX]);
+        assert_eq!(signed, [i64::MIN, -3, -3, 0, 7, i64::MAX]);
+        assert_eq!(small, [i16::MIN, -1, 1, i16::MAX]);
+    }
+
+    #[test]
+    fn test_lsd_radix_sort_floats() {
+        let mut floats = Vec::from([
+            1.5f64,
+            -0.0,
+            f64::INFINITY,
+            0.0,
+            -2.25,
+            f64::NAN,
+            f64::MIN_POSITIVE,
+            -f64::NAN,
+            f64::NEG_INFINITY,
+            -1e-300,
+            1e300,
+        ]);
+        let mut expected = floats.clone();
+        let mut singles = Vec::from([3.5f32, -1.0, 0.0, -0.0, f32::MAX, f32::MIN]);
+
+        lsd_radix_sort(&mut floats);
+        lsd_radix_sort(&mut singles);
+        expected.sort_by(|a, b| a.total_cmp(b));
+
+        let bits = |values: &[f64]| values.iter().map(|v| v.to_bits()).collect::<Vec<_>>();
+
+        assert_eq!(bits(&floats), bits(&expected));
+        assert!(floats[0].is_nan() && floats[0].is_sign_negative());
+        assert!(floats[10].is_nan() && floats[10].is_sign_positive());
+        assert_eq!(singles, [f32::MIN, -1.0, -0.0, 0.0, 3.5, f32::MAX]);
+        assert!(singles[2].is_sign_negative() && singles[3].is_sign_positive());
+    }
+
+    #[test]
+    fn test_lsd_radix_sort_steps() {
+        let (traced, events) = lsd_radix_sort_steps(&[0x0201u16, 0x0102, 0x0101], collect_trace);
+
+        assert_eq!(traced, [0x0101, 0x0102, 0x0201]);
+        assert_eq!(
+            events,
+            [
+                SortEvent::Write(0, 0x0201),
+                SortEvent::Write(1, 0x0101),
+                SortEvent::Write(2, 0x0102),
+                SortEvent::Write(0, 0x0101),
+                SortEvent::Write(1, 0x0102),
+                SortEvent::Write(2, 0x0201),
+            ]
+        );
+        assert_eq!(lsd_radix_sort_steps(&[1u8, 2, 3], |trace| trace.sorted().to_vec()), [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_lsd_radix_sort_skipped_passes() {
+        // Only the lowest byte differs, so a single pass runs and the result ends up in scratch
+        let mut low = Vec::from([5u64, 3, 4, 1, 2]);
+        // Only the second byte differs
+        let mut high = Vec::from([0x0300u32, 0x0100, 0x0200]);
+        let mut same = Vec::from([7usize; 4]);
+
+        lsd_radix_sort(&mut low);
+        lsd_radix_sort(&mut high);
+        lsd_radix_sort(&mut same);
+
+        assert_eq!(low, [1, 2, 3, 4, 5]);
+        assert_eq!(high, [0x0100, 0x0200, 0x0300]);
+        assert_eq!(same, [7; 4]);
+    }
+
+    /// Pseudo-random words over a small alphabet so that long shared prefixes are common
+    fn words(count: usize) -> Vec<String> {
+        let mut state: u32 = 12345;
+
+        return (0..count)
+            .map(|_| {
+                state = state.wrapping_mul(1103515245).wrapping_add(12345);
+
+                let len = (state >> 16) as usize % 6;
+
+                return (0..len)
+                    .map(|_| {
+                        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+
+                        return (b'a' + (state >> 16) as u8 % 3) as char;
+                    })
+                    .collect();
+            })
+            .collect();
+    }
+
+    #[test]
+    fn test_msd_radix_sort() {
+        let mut small = Vec::from(["b", "abc", "", "ab", "a", "ab"]);
+        let mut bytes: Vec<&[u8]> = Vec::from([&[255u8, 0][..], &[0], &[255], &[]]);
+        let mut large = words(500);
+        let mut expected = large.clone();
+
+        msd_radix_sort(&mut small);
+        msd_radix_sort(&mut bytes);
+        msd_radix_sort(&mut large);
+        expected.sort();
+
+        assert_eq!(small, ["", "a", "ab", "ab", "abc", "b"]);
+        assert_eq!(bytes, [&[][..], &[0], &[255], &[255, 0]]);
+        assert_eq!(large, expected);
+    }
 
     #[test]
-    fn test_nth_digit() {
-        assert_eq!(nth_digit(1, 1), 1);
-        assert_eq!(nth_digit(123, 10), 2);
-        assert_eq!(nth_digit(123, 100), 1);
-        assert_eq!(nth_digit(123, 1000), 0);
+    fn test_american_flag_sort() {
+        let mut small = Vec::from(["b", "abc", "", "ab", "a", "ab"]);
+        let mut large = words(500);
+        let mut expected = large.clone();
+
+        american_flag_sort(&mut small);
+        american_flag_sort(&mut large);
+        expected.sort();
+
+        assert_eq!(small, ["", "a", "ab", "ab", "abc", "b"]);
+        assert_eq!(large, expected);
+    }
+
+    #[test]
+    fn test_msd_long_shared_prefix() {
+        // One bucket level per shared byte, far more levels than the call stack could recurse
+        let prefix = "x".repeat(50_000);
+        let mut keys: Vec<String> = (0..20).map(|i| format!("{}{}", prefix, ["b", "a", ""][i % 3])).collect();
+        let mut expected = keys.clone();
+
+        expected.sort();
+
+        let mut flagged = keys.clone();
+
+        msd_radix_sort(&mut keys);
+        american_flag_sort(&mut flagged);
+
+        assert_eq!(keys, expected);
+        assert_eq!(flagged, expected);
+    }
+
+    #[test]
+    fn test_radix_sort_by_key() {
+        let mut records: Vec<(String, usize)> = words(200).into_iter().zip(0..).collect();
+        let mut expected = records.clone();
+
+        radix_sort_by_key(&mut records, |record| record.0.clone());
+        expected.sort_by(|a, b| a.0.cmp(&b.0));
+
+        // `sort_by` is stable, so this also checks that equal keys keep their original order
+        assert_eq!(records, expected);
     }
 
     #[test]
-    fn test_get_longest_number_digits() {
-        assert_eq!(get_longest_number_digits(&[1, 0, 1]), 1);
-        assert_eq!(get_longest_number_digits(&[123, 456, 9999]), 4);
+    fn test_msd_radix_sort_steps() {
+        let keys = words(500);
+        let mut sorted = keys.clone();
+
+        msd_radix_sort(&mut sorted);
+
+        let mut replayed = keys.clone();
+        let (traced, events) = msd_radix_sort_steps(&keys, collect_trace);
+
+        assert_eq!(traced, sorted);
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, sorted);
+
+        // The elements of a trace may borrow from the caller
+        let borrowed: Vec<&str> = keys.iter().map(String::as_str).collect();
+        let mut replayed = borrowed.clone();
+        let (traced, events) = american_flag_sort_steps(&borrowed, collect_trace);
+
+        assert_eq!(traced, sorted);
+        assert!(events.iter().all(|event| !matches!(event, SortEvent::Write(..))));
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, sorted);
+    }
+
+    #[test]
+    fn test_radix_sort_by_key_steps() {
+        let records: Vec<(String, usize)> = words(200).into_iter().zip(0..).collect();
+        let mut expected = records.clone();
+        let mut replayed = records.clone();
+        let (traced, events) = radix_sort_by_key_steps(&records, |record| record.0.clone(), collect_trace);
+
+        radix_sort_by_key(&mut expected, |record| record.0.clone());
+
+        assert_eq!(traced, expected);
+
+        replay(&mut replayed, events);
+        assert_eq!(replayed, expected);
     }
 
     #[test]
@@ -0,0 +1,142 @@
+use crate::binary_search::search_first_true;
+
+/// Index where the sorted order of a rotated sorted array starts, so that `arr[k..]` followed by
+/// `arr[..k]` is sorted, or 0 when `arr` isn't rotated
+///
+/// Takes O(log n) comparisons, but duplicates can force it to O(n): in `[1, 1, 0, 1, 1]` only
+/// looking at every element tells where the 0 is.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::rotated_search::rotation_point;
+///
+/// assert_eq!(rotation_point(&[15, 18, 22, 3, 7, 9]), 3);
+/// assert_eq!(rotation_point(&[3, 7, 9]), 0);
+/// ```
+pub fn rotation_point<T: Ord>(arr: &[T]) -> usize {
+    if arr.is_empty() {
+        return 0;
+    }
+
+    let mut min = 0;
+    let mut max = arr.len() - 1;
+
+    // The rotation point is always in min..=max
+    while min < max {
+        let idx = min + (max - min) / 2;
+
+        if arr[idx] > arr[max] {
+            min = idx + 1;
+        } else if arr[idx] < arr[max] {
+            max = idx;
+        } else if arr[max - 1] > arr[max] {
+            return max;
+        } else {
+            // arr[max] equals arr[idx] and isn't the rotation point, so it can be dropped
+            max -= 1;
+        }
+    }
+
+    return min;
+}
+
+/// Searches a sorted array that has been rotated, such as `[4, 5, 6, 1, 2, 3]`, for `target`
+///
+/// Finds the [`rotation_point`] and binary searches the array in its sorted order. Returns `Ok`
+/// with the index of the first equal element in that order, or `Err` with an index where
+/// `target` could be inserted so that the array stays a rotated sorted array.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::rotated_search::rotated_search;
+///
+/// let nums = [15, 18, 22, 3, 7, 9];
+///
+/// assert_eq!(rotated_search(&nums, &7), Ok(4));
+/// assert_eq!(rotated_search(&nums, &16), Err(1));
+/// assert_eq!(rotated_search(&nums, &1), Err(3));
+/// ```
+pub fn rotated_search<T: Ord>(arr: &[T], target: &T) -> Result<usize, usize> {
+    let len = arr.len();
+    let start = rotation_point(arr);
+    let physical = |logical: usize| if start + logical < len { start + logical } else { start + logical - len };
+    let idx = search_first_true(0..len, |logical| arr[physical(logical)] >= *target);
+
+    if idx < len && arr[physical(idx)] == *target {
+        return Ok(physical(idx));
+    }
+
+    // Past the end of the sorted order wraps around to just before the smallest element, unless
+    // the array isn't rotated
+    return Err(if start + idx <= len { start + idx } else { start + idx - len });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotations(sorted: &[u32]) -> Vec<(usize, Vec<u32>)> {
+        return (0..sorted.len().max(1))
+            .map(|k| {
+                let mut rotated = sorted.to_vec();
+
+                rotated.rotate_left(k);
+
+                return ((sorted.len() - k) % sorted.len().max(1), rotated);
+            })
+            .collect();
+    }
+
+    fn is_rotated_sorted(arr: &[u32]) -> bool {
+        return arr.windows(2).filter(|w| w[0] > w[1]).count() + (arr.len() > 1 && arr[arr.len() - 1] > arr[0]) as usize <= 1;
+    }
+
+    #[test]
+    fn test_rotation_point() {
+        for sorted in [Vec::new(), Vec::from([1]), Vec::from([1, 2, 3, 4, 5, 6, 7]), Vec::from([0, 0, 1, 1, 1, 2, 2])] {
+            for (_, rotated) in rotations(&sorted) {
+                let k = rotation_point(&rotated);
+                let mut unrotated = rotated.clone();
+
+                unrotated.rotate_left(k);
+                assert_eq!(unrotated, sorted, "{:?}", rotated);
+            }
+        }
+
+        assert_eq!(rotation_point(&[1, 1, 0, 1, 1]), 2);
+        assert_eq!(rotation_point(&[1, 1, 1, 1, 0]), 4);
+        assert_eq!(rotation_point(&[2, 2, 2]), 0);
+        assert_eq!(rotation_point(&[0, 0, 1, 0]), 3);
+    }
+
+    #[test]
+    fn test_rotated_search() {
+        let sorted = Vec::from([1, 3, 3, 3, 5, 8, 8, 13]);
+
+        for (start, rotated) in rotations(&sorted) {
+            for target in 0..15 {
+                let result = rotated_search(&rotated, &target);
+
+                match sorted.binary_search(&target) {
+                    Ok(_) => {
+                        let idx = result.unwrap();
+                        let first = sorted.iter().position(|&n| n == target).unwrap();
+
+                        assert_eq!(rotated[idx], target);
+                        assert_eq!(idx, (start + first) % sorted.len());
+                    }
+                    Err(_) => {
+                        let mut inserted = rotated.clone();
+
+                        inserted.insert(result.unwrap_err(), target);
+                        assert!(is_rotated_sorted(&inserted), "{:?} {}", rotated, target);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(rotated_search(&[], &1), Err(0));
+        assert_eq!(rotated_search(&[1, 2, 3], &4), Err(3));
+        assert_eq!(rotated_search(&[1, 1, 0, 1, 1], &0), Ok(2));
+    }
+}
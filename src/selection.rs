@@ -0,0 +1,335 @@
+use crate::binary_heap::{BinaryHeap, HeapOrder};
+use crate::insertion_sort::insertion_sort;
+use crate::quick_sort::{median_of_three, partition, quick_sort_in_place};
+
+/// Index of a pivot that is guaranteed to have at least 30% of `arr` on either side
+///
+/// Moves the median of every group of five to the front of `arr` and selects the median of
+/// those medians.
+fn median_of_medians<T: PartialOrd>(arr: &mut [T]) -> usize {
+    if arr.len() <= 5 {
+        insertion_sort(arr);
+
+        return arr.len() / 2;
+    }
+
+    let mut medians = 0;
+
+    for start in (0..arr.len()).step_by(5) {
+        let end = (start + 5).min(arr.len());
+
+        insertion_sort(&mut arr[start..end]);
+        arr.swap(medians, start + (end - start) / 2);
+        medians += 1;
+    }
+
+    select(&mut arr[..medians], medians / 2, 0);
+
+    return medians / 2;
+}
+
+/// How many times the length of the input quickselect may partition in total before
+/// [`select_nth`] switches to median-of-medians pivots. Median-of-three pivots need about 2.75n
+/// on average.
+const SELECT_WORK_FACTOR: usize = 4;
+
+/// Quickselect that falls back to median-of-medians pivots once its partitions have gone over
+/// `budget` elements in total
+fn select<T: PartialOrd>(arr: &mut [T], k: usize, mut budget: usize) {
+    let mut arr = arr;
+    let mut k = k;
+
+    while arr.len() > 1 {
+        let pivot = if budget > 0 { median_of_three(arr) } else { median_of_medians(arr) };
+
+        budget = budget.saturating_sub(arr.len());
+
+        let (lt, gt) = partition(arr, pivot);
+
+        if k < lt {
+            arr = &mut arr[..lt];
+        } else if k >= gt {
+            arr = &mut arr[gt..];
+            k -= gt;
+        } else {
+            return;
+        }
+    }
+}
+
+/// Reorders `arr` so that the element at index `k` is the one that would be there if `arr` was
+/// sorted, with no greater elements before it and no smaller ones after it
+///
+/// Runs quickselect with median-of-three pivots, switching to median-of-medians pivots once the
+/// partitions have gone over 4n elements in total. Both phases take linear time, so the worst case
+/// is O(n).
+///
+/// # Panics
+/// When `k >= arr.len()`.
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([9, 1, 8, 2, 7, 3]);
+///
+/// assert_eq!(*algorithms_exercises::selection::select_nth(&mut nums, 2), 3);
+/// assert!(nums[..2].iter().all(|&n| n <= 3));
+/// assert!(nums[3..].iter().all(|&n| n >= 3));
+/// ```
+pub fn select_nth<T: PartialOrd>(arr: &mut [T], k: usize) -> &T {
+    assert!(k < arr.len(), "index {} out of range for slice of length {}", k, arr.len());
+
+    select(arr, k, SELECT_WORK_FACTOR * arr.len());
+
+    return &arr[k];
+}
+
+/// Sorts the `k` smallest elements of `arr` into `arr[..k]`, leaving the rest in no particular
+/// order
+///
+/// Takes O(n + k log k) time on average instead of the O(n log n) of a full sort. The sort is
+/// not stable.
+pub fn partial_sort<T: PartialOrd>(arr: &mut [T], k: usize) {
+    let k = k.min(arr.len());
+
+    if k < arr.len() {
+        select_nth(arr, k);
+    }
+
+    quick_sort_in_place(&mut arr[..k]);
+}
+
+/// The `k` smallest items of `iter` in ascending order
+///
+/// Keeps at most `k` items in a max-heap, so it needs O(k) memory however long `iter` is. Wrap
+/// the items in `std::cmp::Reverse` to get the `k` largest instead.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::selection::top_k;
+///
+/// assert_eq!(top_k([5, 1, 4, 2, 3], 2), [1, 2]);
+/// assert_eq!(top_k("hello".chars(), 10), ['e', 'h', 'l', 'l', 'o']);
+/// ```
+pub fn top_k<T, I>(iter: I, k: usize) -> Vec<T>
+where
+    T: PartialOrd,
+    I: IntoIterator<Item = T>,
+{
+    if k == 0 {
+        return Vec::new();
+    }
+
+    return smallest_k_heap(iter, k).into_sorted_vec();
+}
+
+/// Max-heap of the `k` smallest items of `iter`, `k` must not be 0
+fn smallest_k_heap<T, I>(iter: I, k: usize) -> BinaryHeap<T>
+where
+    T: PartialOrd,
+    I: IntoIterator<Item = T>,
+{
+    let mut heap = BinaryHeap::new(HeapOrder::Max);
+
+    for item in iter {
+        if heap.len() < k {
+            heap.push(item);
+        } else if heap.peek().is_some_and(|largest| item < *largest) {
+            heap.pop();
+            heap.push(item);
+        }
+    }
+
+    return heap;
+}
+
+/// Element at the `p`-th percentile of `arr` using the nearest-rank method, or `None` when `arr`
+/// is empty
+///
+/// This is the smallest element that is greater than or equal to at least `p` percent of the
+/// elements. `arr` is reordered as by [`select_nth`].
+///
+/// # Panics
+/// When `p` is not between 0 and 100.
+pub fn percentile<T: PartialOrd>(arr: &mut [T], p: f64) -> Option<&T> {
+    assert!((0.0..=100.0).contains(&p), "percentile {} is not between 0 and 100", p);
+
+    if arr.is_empty() {
+        return None;
+    }
+
+    let rank = (p / 100.0 * arr.len() as f64).ceil() as usize;
+
+    return Some(select_nth(arr, rank.max(1) - 1));
+}
+
+/// Median of `arr`, the lower of the two middle elements when its length is even
+///
+/// Same as the 50th [`percentile`].
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([4.5, 1.0, 3.0, 2.5]);
+///
+/// assert_eq!(algorithms_exercises::selection::median(&mut nums), Some(&2.5));
+/// ```
+pub fn median<T: PartialOrd>(arr: &mut [T]) -> Option<&T> {
+    return percentile(arr, 50.0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{generate, Distribution};
+
+    fn assert_selected(arr: &[u64], k: usize) {
+        assert!(arr[..k].iter().all(|n| *n <= arr[k]));
+        assert!(arr[k + 1..].iter().all(|n| *n >= arr[k]));
+    }
+
+    #[test]
+    fn test_select_nth() {
+        assert_eq!(*select_nth(&mut [1], 0), 1);
+        assert_eq!(*select_nth(&mut [2, 2, 2, 1], 1), 2);
+
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 1000, 3);
+            let mut sorted = nums.clone();
+
+            sorted.sort();
+
+            for k in [0, 1, 499, 500, 998, 999] {
+                let mut arr = nums.clone();
+
+                assert_eq!(*select_nth(&mut arr, k), sorted[k], "{} k={}", distribution, k);
+                assert_selected(&arr, k);
+            }
+        }
+    }
+
+    /// Counts the comparisons made between its values in `COMPARISONS`
+    #[derive(PartialEq)]
+    struct Counted(u64);
+
+    thread_local! {
+        static COMPARISONS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    }
+
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            COMPARISONS.with(|count| count.set(count.get() + 1));
+
+            return self.0.partial_cmp(&other.0);
+        }
+    }
+
+    #[test]
+    fn test_select_nth_linear() {
+        const LEN: usize = 20_000;
+
+        for distribution in Distribution::ALL {
+            for k in [0, LEN / 3, LEN / 2, LEN - 1] {
+                let mut arr: Vec<Counted> = generate(distribution, LEN, 10).into_iter().map(Counted).collect();
+
+                COMPARISONS.with(|count| count.set(0));
+                select_nth(&mut arr, k);
+
+                // The inputs that defeat median-of-three pivots need about 20n
+                let comparisons = COMPARISONS.with(|count| count.get());
+
+                assert!(comparisons <= 30 * LEN, "{} k={}: {}", distribution, k, comparisons);
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_select_nth_out_of_range() {
+        select_nth(&mut [1, 2, 3], 3);
+    }
+
+    #[test]
+    fn test_median_of_medians() {
+        // A zero budget uses median-of-medians pivots from the start
+        for distribution in Distribution::ALL {
+            let nums = generate(distribution, 777, 4);
+            let mut sorted = nums.clone();
+
+            sorted.sort();
+
+            for k in [0, 100, 388, 776] {
+                let mut arr = nums.clone();
+
+                select(&mut arr, k, 0);
+                assert_eq!(arr[k], sorted[k], "{} k={}", distribution, k);
+                assert_selected(&arr, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial_sort() {
+        let nums = generate(Distribution::Random, 500, 5);
+        let mut sorted = nums.clone();
+
+        sorted.sort();
+
+        for k in [0, 1, 10, 499, 500, 1000] {
+            let mut arr = nums.clone();
+            let k = k.min(arr.len());
+
+            partial_sort(&mut arr, k);
+            assert_eq!(arr[..k], sorted[..k]);
+
+            arr.sort();
+            assert_eq!(arr, sorted);
+        }
+
+        partial_sort::<u8>(&mut [], 3);
+    }
+
+    #[test]
+    fn test_top_k() {
+        let nums = generate(Distribution::FewUnique, 1000, 6);
+        let mut sorted = nums.clone();
+
+        sorted.sort();
+
+        assert_eq!(top_k(nums.iter().copied(), 0), []);
+        assert_eq!(top_k(nums.iter().copied(), 25), sorted[..25]);
+        assert_eq!(top_k(nums.iter().copied(), 2000), sorted);
+        assert_eq!(
+            top_k(nums.iter().map(|&n| std::cmp::Reverse(n)), 3),
+            sorted.iter().rev().take(3).map(|&n| std::cmp::Reverse(n)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_top_k_memory() {
+        // Every item of a descending stream replaces the largest one kept so far
+        let heap = smallest_k_heap((0..1_000_000u32).rev(), 10);
+
+        assert_eq!(heap.len(), 10);
+        assert_eq!(heap.slot_count(), 10);
+        assert_eq!(heap.into_sorted_vec(), (0..10).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_percentile() {
+        let mut nums: Vec<u32> = (1..=10).rev().collect();
+
+        assert_eq!(percentile(&mut nums, 0.0), Some(&1));
+        assert_eq!(percentile(&mut nums, 10.0), Some(&1));
+        assert_eq!(percentile(&mut nums, 11.0), Some(&2));
+        assert_eq!(percentile(&mut nums, 90.0), Some(&9));
+        assert_eq!(percentile(&mut nums, 100.0), Some(&10));
+        assert_eq!(percentile::<u32>(&mut [], 50.0), None);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(&mut [3, 1, 2]), Some(&2));
+        assert_eq!(median(&mut [4, 1, 3, 2]), Some(&2));
+        assert_eq!(median(&mut [7]), Some(&7));
+        assert_eq!(median::<i32>(&mut []), None);
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt;
+
+use crate::sort_trace::{trace, SortTrace};
+
+/// Gaps Ciura found to work best empirically, extended by multiplying by 2.25
+const CIURA_GAPS: [usize; 9] = [1, 4, 10, 23, 57, 132, 301, 701, 1750];
+
+/// The gaps [`shell_sort_with_gaps`] insertion sorts with, from largest to smallest
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GapSequence {
+    /// `n/2, n/4, …, 1`, Shell's original sequence, O(n²) in the worst case
+    Shell,
+    /// `1, 4, 13, 40, …` (`(3^k - 1) / 2`), O(n^(3/2)) in the worst case
+    Knuth,
+    /// `1, 4, 10, 23, 57, 132, 301, 701, 1750`, then multiplied by 2.25
+    Ciura,
+    /// `1, 8, 23, 77, 281, …` (`4^k + 3·2^(k-1) + 1`), O(n^(4/3)) in the worst case
+    Sedgewick,
+}
+
+impl GapSequence {
+    pub const ALL: [GapSequence; 4] = [
+        GapSequence::Shell,
+        GapSequence::Knuth,
+        GapSequence::Ciura,
+        GapSequence::Sedgewick,
+    ];
+
+    /// The gaps smaller than `len` in decreasing order, ending with 1 unless `len <= 1`
+    ///
+    /// # Example
+    /// ```
+    /// use algorithms_exercises::shell_sort::GapSequence;
+    ///
+    /// assert_eq!(GapSequence::Shell.gaps(20), [10, 5, 2, 1]);
+    /// assert_eq!(GapSequence::Knuth.gaps(20), [13, 4, 1]);
+    /// ```
+    pub fn gaps(self, len: usize) -> Vec<usize> {
+        let mut gaps = Vec::new();
+
+        if len <= 1 {
+            return gaps;
+        }
+
+        match self {
+            GapSequence::Shell => {
+                let mut gap = len / 2;
+
+                while gap > 0 {
+                    gaps.push(gap);
+                    gap /= 2;
+                }
+
+                return gaps;
+            }
+            GapSequence::Knuth => {
+                let mut gap = 1;
+
+                while gap < len {
+                    gaps.push(gap);
+                    gap = gap.saturating_mul(3).saturating_add(1);
+                }
+            }
+            GapSequence::Ciura => {
+                gaps.extend(CIURA_GAPS.iter().copied().take_while(|&gap| gap < len));
+
+                let mut gap = (CIURA_GAPS[CIURA_GAPS.len() - 1] as f64 * 2.25) as usize;
+
+                while gap < len {
+                    gaps.push(gap);
+                    gap = (gap as f64 * 2.25) as usize;
+                }
+            }
+            GapSequence::Sedgewick => {
+                let mut gap = 1;
+                let mut k = 1;
+
+                while gap < len {
+                    gaps.push(gap);
+                    gap = 4usize
+                        .saturating_pow(k)
+                        .saturating_add(3usize.saturating_mul(1 << (k - 1)))
+                        .saturating_add(1);
+                    k += 1;
+                }
+            }
+        }
+
+        gaps.reverse();
+
+        return gaps;
+    }
+}
+
+impl fmt::Display for GapSequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            GapSequence::Shell => "shell",
+            GapSequence::Knuth => "knuth",
+            GapSequence::Ciura => "ciura",
+            GapSequence::Sedgewick => "sedgewick",
+        };
+
+        return write!(f, "{}", name);
+    }
+}
+
+/// Shell sort: insertion sort over elements `gap` apart, for every gap of `sequence`
+///
+/// The large gaps move elements far towards their place early, so the final pass with a gap of
+/// 1 (a plain insertion sort) has little left to do. The sort is not stable.
+pub fn shell_sort_with_gaps<T>(arr: &mut [T], sequence: GapSequence)
+where
+    T: PartialOrd,
+{
+    for gap in sequence.gaps(arr.len()) {
+        for index in gap..arr.len() {
+            let mut j = index;
+
+            while j >= gap && arr[j - gap] > arr[j] {
+                arr.swap(j, j - gap);
+                j -= gap;
+            }
+        }
+    }
+}
+
+/// Shell sort with Ciura's gap sequence, see [`shell_sort_with_gaps`]
+///
+/// # Example
+/// ```
+/// let mut nums = Vec::from([9, 4, 7, 1, 8, 2]);
+///
+/// algorithms_exercises::shell_sort::shell_sort(&mut nums);
+/// assert_eq!(nums, Vec::from([1, 2, 4, 7, 8, 9]));
+/// ```
+pub fn shell_sort<T>(arr: &mut [T])
+where
+    T: PartialOrd,
+{
+    shell_sort_with_gaps(arr, GapSequence::Ciura);
+}
+
+/// Runs [`shell_sort_with_gaps`] on a copy of `arr` and lends `consume` its trace, recording every
+/// comparison and swap
+pub fn shell_sort_steps<T, C, R>(arr: &[T], sequence: GapSequence, consume: C) -> R
+where
+    T: PartialOrd + Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+{
+    return trace(arr, consume, move |arr, tracer| {
+        for gap in sequence.gaps(arr.len()) {
+            for index in gap..arr.len() {
+                let mut j = index;
+
+                while j >= gap {
+                    tracer.compare(j - gap, j);
+
+                    if arr[j - gap] <= arr[j] {
+                        break;
+                    }
+
+                    tracer.swap(arr, j, j - gap);
+                    j -= gap;
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sort_trace::{collect_trace, replay};
+    use crate::test_support::{generate, Distribution};
+
+    #[test]
+    fn test_gaps() {
+        assert_eq!(GapSequence::Shell.gaps(9), [4, 2, 1]);
+        assert_eq!(GapSequence::Knuth.gaps(41), [40, 13, 4, 1]);
+        assert_eq!(GapSequence::Ciura.gaps(24), [23, 10, 4, 1]);
+        assert_eq!(GapSequence::Ciura.gaps(10_000), [8858, 3937, 1750, 701, 301, 132, 57, 23, 10, 4, 1]);
+        assert_eq!(GapSequence::Sedgewick.gaps(300), [281, 77, 23, 8, 1]);
+
+        for sequence in GapSequence::ALL {
+            assert_eq!(sequence.gaps(0), []);
+            assert_eq!(sequence.gaps(1), []);
+            assert_eq!(sequence.gaps(2), [1]);
+            assert!(sequence.gaps(usize::MAX).windows(2).all(|w| w[0] > w[1]), "{}", sequence);
+        }
+    }
+
+    #[test]
+    fn test_shell_sort_with_gaps() {
+        for sequence in GapSequence::ALL {
+            for distribution in Distribution::ALL {
+                let mut nums = generate(distribution, 1000, 12);
+                let mut expected = nums.clone();
+
+                expected.sort();
+                shell_sort_with_gaps(&mut nums, sequence);
+
+                assert_eq!(nums, expected, "{} {}", sequence, distribution);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shell_sort() {
+        let mut nums1: Vec<usize> = Vec::new();
+        let mut nums2 = Vec::from([10, 5, 3, 8, 2, 6, 4, 7, 9, 1]);
+        let mut chars = Vec::from(['g', 'd', 'a', 'b', 'f']);
+
+        shell_sort(&mut nums1);
+        shell_sort(&mut nums2);
+        shell_sort(&mut chars);
+
+        assert_eq!(nums1, Vec::new());
+        assert_eq!(nums2, Vec::from([1, 2, 3, 4, 5, 6, 7, 8, 9, 10]));
+        assert_eq!(chars, Vec::from(['a', 'b', 'd', 'f', 'g']));
+    }
+
+    #[test]
+    fn test_shell_sort_steps() {
+        for sequence in GapSequence::ALL {
+            let nums = generate(Distribution::Random, 200, 4);
+            let (traced, events) = shell_sort_steps(&nums, sequence, collect_trace);
+            let mut sorted = nums.clone();
+            let mut replayed = nums.clone();
+
+            shell_sort_with_gaps(&mut sorted, sequence);
+
+            assert_eq!(traced, sorted, "{}", sequence);
+
+            replay(&mut replayed, events);
+            assert_eq!(replayed, sorted, "{}", sequence);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use std::mem::size_of;
+
+/// Counters collected by the `*_with_stats` variants of the sorting algorithms
+///
+/// The instrumented variants are separate functions, so the plain sorts pay nothing for them.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::{bubble_sort::bubble_sort_with_stats, sort_stats::SortStats};
+///
+/// let mut nums = Vec::from([3, 2, 1]);
+/// let mut stats = SortStats::new();
+///
+/// bubble_sort_with_stats(&mut nums, &mut stats);
+/// assert_eq!(stats.comparisons, 3);
+/// assert_eq!(stats.swaps, 3);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SortStats {
+    /// Number of comparisons between two elements
+    pub comparisons: usize,
+    /// Number of swaps of two elements in place
+    pub swaps: usize,
+    /// Number of elements copied into another buffer
+    pub moves: usize,
+    /// Deepest level of nested recursive calls, 0 for iterative algorithms
+    pub max_depth: usize,
+    /// Total capacity, in bytes, of the buffers allocated by the algorithm
+    pub bytes_allocated: usize,
+}
+
+impl SortStats {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    pub(crate) fn less<T: PartialOrd>(&mut self, a: &T, b: &T) -> bool {
+        self.comparisons += 1;
+
+        return a < b;
+    }
+
+    pub(crate) fn greater<T: PartialOrd>(&mut self, a: &T, b: &T) -> bool {
+        self.comparisons += 1;
+
+        return a > b;
+    }
+
+    pub(crate) fn swap<T>(&mut self, arr: &mut [T], a: usize, b: usize) {
+        self.swaps += 1;
+        arr.swap(a, b);
+    }
+
+    /// Records a buffer of `capacity` elements of type `T`
+    pub(crate) fn allocate<T>(&mut self, capacity: usize) {
+        self.bytes_allocated += capacity * size_of::<T>();
+    }
+
+    pub(crate) fn enter(&mut self, depth: usize) {
+        self.max_depth = self.max_depth.max(depth);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_stats() {
+        let mut stats = SortStats::new();
+        let mut nums = [1u64, 2];
+
+        assert!(stats.less(&1, &2));
+        assert!(!stats.greater(&1, &2));
+        stats.swap(&mut nums, 0, 1);
+        stats.allocate::<u64>(3);
+        stats.enter(2);
+        stats.enter(1);
+
+        assert_eq!(nums, [2, 1]);
+        assert_eq!(
+            stats,
+            SortStats {
+                comparisons: 2,
+                swaps: 1,
+                moves: 0,
+                max_depth: 2,
+                bytes_allocated: 24,
+            }
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use std::collections::VecDeque;
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+/// A single step of a sorting algorithm, indices refer to positions in the array being sorted
+///
+/// Replaying the `Swap` and `Write` events of a trace on a copy of the input (see [`replay`])
+/// reproduces every intermediate state of the array and ends with the sorted result. Algorithms
+/// that merge or partition out of place compare elements at the positions they occupied when the
+/// merge or partition started, then write the result back.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SortEvent<T> {
+    Compare(usize, usize),
+    Swap(usize, usize),
+    Write(usize, T),
+    /// `(lo, hi, pivot)`: `lo..hi` has been partitioned and the pivot now sits at index `pivot`
+    PartitionDone(usize, usize, usize),
+    /// `(lo, mid, hi)`: `lo..mid` and `mid..hi` are about to be merged into `lo..hi`
+    MergeRange(usize, usize, usize),
+}
+
+/// Number of events a sort can get ahead of the code consuming its trace before it has to wait
+/// for it
+const TRACE_BUFFER: usize = 1024;
+
+/// Unwinds a traced sort whose [`SortTrace`] has been dropped, nobody is listening anymore
+struct TraceDropped;
+
+/// What a traced sort sends to its [`SortTrace`]: its events, then the sorted array or the panic
+/// that stopped it
+enum Message<T> {
+    Event(SortEvent<T>),
+    Done(thread::Result<Vec<T>>),
+}
+
+/// Sends the events of a sort on its working array to the [`SortTrace`] it runs for
+pub(crate) struct Tracer<T> {
+    messages: SyncSender<Message<T>>,
+}
+
+impl<T: Clone> Tracer<T> {
+    pub(crate) fn push(&mut self, event: SortEvent<T>) {
+        if self.messages.send(Message::Event(event)).is_err() {
+            // Stops the sort without running the panic hook, there's no error to report
+            panic::resume_unwind(Box::new(TraceDropped));
+        }
+    }
+
+    pub(crate) fn compare(&mut self, i: usize, j: usize) {
+        self.push(SortEvent::Compare(i, j));
+    }
+
+    pub(crate) fn swap(&mut self, arr: &mut [T], i: usize, j: usize) {
+        self.push(SortEvent::Swap(i, j));
+        arr.swap(i, j);
+    }
+
+    pub(crate) fn write(&mut self, arr: &mut [T], i: usize, value: T) {
+        self.push(SortEvent::Write(i, value.clone()));
+        arr[i] = value;
+    }
+}
+
+/// Runs `sort` on a copy of `arr` in a thread of its own and calls `consume` with its trace,
+/// handing the events over as they happen
+///
+/// The thread is scoped to this call, which is why the trace is lent to a callback instead of
+/// returned: the elements only need to be `Send`, not `'static`, so they may borrow from the
+/// caller. The sort stops once `consume` returns.
+pub(crate) fn trace<T, C, R, F>(arr: &[T], consume: C, sort: F) -> R
+where
+    T: Clone + Send,
+    C: FnOnce(&mut SortTrace<T>) -> R,
+    F: FnOnce(&mut [T], &mut Tracer<T>) + Send,
+{
+    let (sender, messages) = mpsc::sync_channel(TRACE_BUFFER);
+    let mut arr = arr.to_vec();
+
+    return thread::scope(|scope| {
+        scope.spawn(move || {
+            let mut tracer = Tracer { messages: sender };
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                sort(&mut arr, &mut tracer);
+
+                return arr;
+            }));
+
+            // Fails when the trace was dropped first, then nobody wants the result either
+            let _ = tracer.messages.send(Message::Done(result));
+        });
+
+        let mut trace = SortTrace {
+            messages,
+            pending: VecDeque::new(),
+            sorted: None,
+        };
+
+        return consume(&mut trace);
+    });
+}
+
+/// Runs a trace to the end, returning its sorted array and all of its events
+#[cfg(test)]
+pub(crate) fn collect_trace<T: Clone>(trace: &mut SortTrace<T>) -> (Vec<T>, Vec<SortEvent<T>>) {
+    let sorted = trace.sorted().to_vec();
+
+    return (sorted, trace.collect());
+}
+
+/// Iterator over the steps of a sort, lent to the callback of the `*_steps` functions
+///
+/// The sort runs in a thread of its own and produces events as they are consumed, staying at
+/// most a fixed number of events ahead, so a trace takes constant memory however many steps the
+/// sort takes. The elements must therefore be `Send`. A panic in the sort, for example from a
+/// comparison, is passed on to the code iterating over the trace.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::bubble_sort::bubble_sort_steps;
+///
+/// let words = String::from("pear fig apple");
+/// let parts: Vec<&str> = words.split(' ').collect();
+/// let swaps = bubble_sort_steps(&parts, |trace| {
+///     assert_eq!(trace.sorted(), ["apple", "fig", "pear"]);
+///
+///     return trace.filter(|event| matches!(event, algorithms_exercises::sort_trace::SortEvent::Swap(..))).count();
+/// });
+///
+/// assert_eq!(swaps, 3);
+/// ```
+pub struct SortTrace<T> {
+    messages: Receiver<Message<T>>,
+    /// Events received by [`SortTrace::sorted`] before the iterator got to them
+    pending: VecDeque<SortEvent<T>>,
+    /// The result of the sort, once it has finished
+    sorted: Option<Vec<T>>,
+}
+
+impl<T> SortTrace<T> {
+    /// Receives the next event, or the end of the sort, passing its panic on if it had one
+    fn receive(&mut self) -> Option<SortEvent<T>> {
+        if self.sorted.is_some() {
+            return None;
+        }
+
+        match self.messages.recv().expect("the sort sends its result before hanging up") {
+            Message::Event(event) => return Some(event),
+            Message::Done(Ok(sorted)) => self.sorted = Some(sorted),
+            Message::Done(Err(payload)) => panic::resume_unwind(payload),
+        }
+
+        return None;
+    }
+
+    /// The sorted array the trace ends with
+    ///
+    /// Runs the rest of the sort, keeping the events the iterator hasn't returned yet in memory.
+    pub fn sorted(&mut self) -> &[T] {
+        while self.sorted.is_none() {
+            if let Some(event) = self.receive() {
+                self.pending.push_back(event);
+            }
+        }
+
+        return self.sorted.as_deref().unwrap();
+    }
+}
+
+impl<T> Iterator for SortTrace<T> {
+    type Item = SortEvent<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+
+        return self.receive();
+    }
+}
+
+/// Applies the `Swap` and `Write` events of a trace to `arr`
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::{bubble_sort::bubble_sort_steps, sort_trace::replay};
+///
+/// let mut nums = Vec::from([3, 1, 2]);
+/// let steps = bubble_sort_steps(&nums, |trace| trace.collect::<Vec<_>>());
+///
+/// replay(&mut nums, steps);
+/// assert_eq!(nums, [1, 2, 3]);
+/// ```
+pub fn replay<T, I>(arr: &mut [T], events: I)
+where
+    I: IntoIterator<Item = SortEvent<T>>,
+{
+    for event in events {
+        match event {
+            SortEvent::Swap(i, j) => arr.swap(i, j),
+            SortEvent::Write(i, value) => arr[i] = value,
+            _ => {}
+        }
+    }
+}
+
+/// Value that can be written as JSON
+pub trait JsonValue {
+    fn to_json(&self) -> String;
+}
+
+macro_rules! impl_json_value_display {
+    ($($t:ty),*) => {
+        $(
+            impl JsonValue for $t {
+                fn to_json(&self) -> String {
+                    return self.to_string();
+                }
+            }
+        )*
+    };
+}
+
+impl_json_value_display!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, bool);
+
+macro_rules! impl_json_value_float {
+    ($($t:ty),*) => {
+        $(
+            impl JsonValue for $t {
+                /// JSON has no NaN or infinities, so they are written as `null`
+                fn to_json(&self) -> String {
+                    if self.is_finite() {
+                        return self.to_string();
+                    }
+
+                    return String::from("null");
+                }
+            }
+        )*
+    };
+}
+
+impl_json_value_float!(f32, f64);
+
+impl JsonValue for str {
+    fn to_json(&self) -> String {
+        let mut result = String::with_capacity(self.len() + 2);
+
+        result.push('"');
+
+        for c in self.chars() {
+            match c {
+                '"' => result.push_str("\\\""),
+                '\\' => result.push_str("\\\\"),
+                '\n' => result.push_str("\\n"),
+                '\r' => result.push_str("\\r"),
+                '\t' => result.push_str("\\t"),
+                c if (c as u32) < 0x20 => result.push_str(&format!("\\u{:04x}", c as u32)),
+                c => result.push(c),
+            }
+        }
+
+        result.push('"');
+
+        return result;
+    }
+}
+
+impl JsonValue for String {
+    fn to_json(&self) -> String {
+        return self.as_str().to_json();
+    }
+}
+
+impl JsonValue for char {
+    fn to_json(&self) -> String {
+        return self.to_string().to_json();
+    }
+}
+
+impl<T: JsonValue + ?Sized> JsonValue for &T {
+    fn to_json(&self) -> String {
+        return (**self).to_json();
+    }
+}
+
+impl<T: JsonValue> SortEvent<T> {
+    /// The event as a single-line JSON object, e.g. `{"event":"swap","i":0,"j":1}`
+    pub fn to_json(&self) -> String {
+        return match self {
+            SortEvent::Compare(i, j) => format!(r#"{{"event":"compare","i":{},"j":{}}}"#, i, j),
+            SortEvent::Swap(i, j) => format!(r#"{{"event":"swap","i":{},"j":{}}}"#, i, j),
+            SortEvent::Write(i, value) => {
+                format!(r#"{{"event":"write","index":{},"value":{}}}"#, i, value.to_json())
+            }
+            SortEvent::PartitionDone(lo, hi, pivot) => format!(
+                r#"{{"event":"partition_done","lo":{},"hi":{},"pivot":{}}}"#,
+                lo, hi, pivot
+            ),
+            SortEvent::MergeRange(lo, mid, hi) => format!(
+                r#"{{"event":"merge_range","lo":{},"mid":{},"hi":{}}}"#,
+                lo, mid, hi
+            ),
+        };
+    }
+}
+
+/// Writes a trace as JSON lines, one event per line
+pub fn write_json_lines<T, I, W>(events: I, writer: &mut W) -> io::Result<()>
+where
+    T: JsonValue,
+    I: IntoIterator<Item = SortEvent<T>>,
+    W: Write,
+{
+    for event in events {
+        writeln!(writer, "{}", event.to_json())?;
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace() {
+        let events = trace(
+            &[1, 2, 3],
+            |trace| {
+                assert_eq!(trace.next(), Some(SortEvent::Compare(0, 1)));
+                assert_eq!(trace.sorted(), [3, 7, 1]);
+
+                return trace.collect::<Vec<_>>();
+            },
+            |arr, tracer| {
+                tracer.compare(0, 1);
+                tracer.swap(arr, 0, 2);
+                tracer.write(arr, 1, 7);
+            },
+        );
+
+        assert_eq!(events, [SortEvent::Swap(0, 2), SortEvent::Write(1, 7)]);
+    }
+
+    #[test]
+    fn test_trace_is_lazy() {
+        let (sender, receiver) = mpsc::channel();
+        let event = trace(
+            &[0],
+            |trace| trace.nth(10_000),
+            move |arr, tracer| {
+                for _ in 0..usize::MAX {
+                    tracer.write(arr, 0, 1);
+                }
+
+                sender.send(()).unwrap();
+            },
+        );
+
+        assert_eq!(event, Some(SortEvent::Write(0, 1)));
+
+        // The sort stopped at its next event instead of running to the end
+        assert!(receiver.recv().is_err());
+    }
+
+    #[test]
+    fn test_trace_borrowed() {
+        let text = String::from("b c a");
+        let words: Vec<&str> = text.split(' ').collect();
+        let sorted = trace(&words, |trace| trace.sorted().to_vec(), |arr, tracer| tracer.swap(arr, 0, 2));
+
+        assert_eq!(sorted, ["a", "c", "b"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "comparison failed")]
+    fn test_trace_panic() {
+        trace(
+            &[1, 2],
+            |trace| trace.for_each(drop),
+            |_, tracer: &mut Tracer<i32>| {
+                tracer.compare(0, 1);
+                panic!("comparison failed");
+            },
+        );
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut arr = Vec::from(['a', 'b', 'c']);
+
+        replay(
+            &mut arr,
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(0, 2),
+                SortEvent::MergeRange(0, 1, 3),
+                SortEvent::Write(1, 'z'),
+                SortEvent::PartitionDone(0, 3, 1),
+            ],
+        );
+
+        assert_eq!(arr, ['c', 'z', 'a']);
+    }
+
+    #[test]
+    fn test_write_json_lines() {
+        let mut output = Vec::new();
+
+        write_json_lines(
+            [
+                SortEvent::Compare(0, 1),
+                SortEvent::Swap(1, 2),
+                SortEvent::Write(3, "a \"b\"\n"),
+                SortEvent::PartitionDone(0, 4, 2),
+                SortEvent::MergeRange(0, 2, 4),
+            ],
+            &mut output,
+        )
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            concat!(
+                "{\"event\":\"compare\",\"i\":0,\"j\":1}\n",
+                "{\"event\":\"swap\",\"i\":1,\"j\":2}\n",
+                "{\"event\":\"write\",\"index\":3,\"value\":\"a \\\"b\\\"\\n\"}\n",
+                "{\"event\":\"partition_done\",\"lo\":0,\"hi\":4,\"pivot\":2}\n",
+                "{\"event\":\"merge_range\",\"lo\":0,\"mid\":2,\"hi\":4}\n",
+            )
+        );
+        assert_eq!(SortEvent::Write(0, f64::NAN).to_json(), r#"{"event":"write","index":0,"value":null}"#);
+        assert_eq!(SortEvent::Write(0, -1.5).to_json(), r#"{"event":"write","index":0,"value":-1.5}"#);
+    }
+}
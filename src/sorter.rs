@@ -0,0 +1,811 @@
+use crate::binary_heap::heap_sort;
+use crate::bubble_sort::{bubble_sort, cocktail_shaker_sort};
+use crate::bucket_sort::bucket_sort;
+use crate::comb_sort::comb_sort;
+use crate::counting_sort::counting_sort;
+use crate::gnome_sort::gnome_sort;
+use crate::insertion_sort::{binary_insertion_sort, insertion_sort};
+use crate::merge_sort::merge_sort;
+use crate::parallel_sort::{parallel_merge_sort, parallel_quick_sort, parallel_radix_sort, ParallelConfig};
+use crate::quick_sort::{intro_sort, quick_sort, quick_sort_in_place, stable_quick_sort};
+use crate::radix_sort::{american_flag_sort, lsd_radix_sort, msd_radix_sort, RadixKey};
+use crate::shell_sort::{shell_sort_with_gaps, GapSequence};
+
+/// Time and extra space an algorithm needs, in big-O notation
+///
+/// For the radix sorts `w` is the length of the keys in bytes, for counting sort `k` is the
+/// number of values between the smallest and the largest key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Complexity {
+    pub best: &'static str,
+    pub average: &'static str,
+    pub worst: &'static str,
+    pub space: &'static str,
+}
+
+/// A sorting algorithm that sorts a slice in place
+///
+/// Algorithms that return a sorted copy are adapted by copying the result back, so every
+/// implementation can be run against the same inputs.
+pub trait Sorter<T> {
+    fn name(&self) -> &'static str;
+
+    /// Whether elements that compare equal keep their relative order
+    fn is_stable(&self) -> bool;
+
+    /// Whether the worst case is O(n²), or no better bound is proven, even if typical inputs
+    /// take far less time
+    fn has_quadratic_worst_case(&self) -> bool;
+
+    fn complexity(&self) -> Complexity;
+
+    fn sort(&self, arr: &mut [T]);
+}
+
+const QUADRATIC: Complexity = Complexity {
+    best: "O(n)",
+    average: "O(n²)",
+    worst: "O(n²)",
+    space: "O(1)",
+};
+
+const RADIX: Complexity = Complexity {
+    best: "O(w·n)",
+    average: "O(w·n)",
+    worst: "O(w·n)",
+    space: "O(n)",
+};
+
+pub struct BubbleSort;
+
+impl<T: PartialOrd> Sorter<T> for BubbleSort {
+    fn name(&self) -> &'static str {
+        return "bubble_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return QUADRATIC;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        bubble_sort(arr);
+    }
+}
+
+pub struct InsertionSort;
+
+impl<T: PartialOrd> Sorter<T> for InsertionSort {
+    fn name(&self) -> &'static str {
+        return "insertion_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return QUADRATIC;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        insertion_sort(arr);
+    }
+}
+
+pub struct CocktailShakerSort;
+
+impl<T: PartialOrd> Sorter<T> for CocktailShakerSort {
+    fn name(&self) -> &'static str {
+        return "cocktail_shaker_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return QUADRATIC;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        cocktail_shaker_sort(arr);
+    }
+}
+
+pub struct GnomeSort;
+
+impl<T: PartialOrd> Sorter<T> for GnomeSort {
+    fn name(&self) -> &'static str {
+        return "gnome_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return QUADRATIC;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        gnome_sort(arr);
+    }
+}
+
+pub struct BinaryInsertionSort;
+
+impl<T: PartialOrd> Sorter<T> for BinaryInsertionSort {
+    fn name(&self) -> &'static str {
+        return "binary_insertion_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n log n)",
+            ..QUADRATIC
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        binary_insertion_sort(arr);
+    }
+}
+
+pub struct CombSort;
+
+impl<T: PartialOrd> Sorter<T> for CombSort {
+    fn name(&self) -> &'static str {
+        return "comb_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n log n)",
+            average: "O(n²/2^p)",
+            worst: "O(n²)",
+            space: "O(1)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        comb_sort(arr);
+    }
+}
+
+/// Shell sort with the given gap sequence
+pub struct ShellSort(pub GapSequence);
+
+impl<T: PartialOrd> Sorter<T> for ShellSort {
+    fn name(&self) -> &'static str {
+        return match self.0 {
+            GapSequence::Shell => "shell_sort_shell",
+            GapSequence::Knuth => "shell_sort_knuth",
+            GapSequence::Ciura => "shell_sort_ciura",
+            GapSequence::Sedgewick => "shell_sort_sedgewick",
+        };
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return self.0 == GapSequence::Shell || self.0 == GapSequence::Ciura;
+    }
+
+    fn complexity(&self) -> Complexity {
+        let (average, worst) = match self.0 {
+            GapSequence::Shell => ("O(n^(3/2))", "O(n²)"),
+            GapSequence::Knuth => ("O(n^(5/4))", "O(n^(3/2))"),
+            // Ciura's gaps were found experimentally, nothing tighter than the O(n²) that holds for
+            // any sequence ending in 1 is proven, so it counts as quadratic even though no input is
+            // known to reach it
+            GapSequence::Ciura => ("O(n^(5/4))", "O(n²)"),
+            GapSequence::Sedgewick => ("O(n^(7/6))", "O(n^(4/3))"),
+        };
+
+        return Complexity {
+            best: "O(n log n)",
+            average,
+            worst,
+            space: "O(1)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        shell_sort_with_gaps(arr, self.0);
+    }
+}
+
+pub struct MergeSort;
+
+impl<T: PartialOrd + Clone> Sorter<T> for MergeSort {
+    fn name(&self) -> &'static str {
+        return "merge_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n log n)",
+            average: "O(n log n)",
+            worst: "O(n log n)",
+            space: "O(n)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        let sorted = merge_sort(arr);
+
+        arr.clone_from_slice(&sorted);
+    }
+}
+
+pub struct QuickSort;
+
+impl<T: PartialOrd + Clone> Sorter<T> for QuickSort {
+    fn name(&self) -> &'static str {
+        return "quick_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n log n)",
+            average: "O(n log n)",
+            worst: "O(n²)",
+            space: "O(n)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        let sorted = quick_sort(arr);
+
+        arr.clone_from_slice(&sorted);
+    }
+}
+
+pub struct StableQuickSort;
+
+impl<T: PartialOrd + Clone> Sorter<T> for StableQuickSort {
+    fn name(&self) -> &'static str {
+        return "stable_quick_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n)",
+            ..Sorter::<T>::complexity(&QuickSort)
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        let sorted = stable_quick_sort(arr);
+
+        arr.clone_from_slice(&sorted);
+    }
+}
+
+pub struct QuickSortInPlace;
+
+impl<T: PartialOrd> Sorter<T> for QuickSortInPlace {
+    fn name(&self) -> &'static str {
+        return "quick_sort_in_place";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n)",
+            average: "O(n log n)",
+            worst: "O(n²)",
+            space: "O(log n)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        quick_sort_in_place(arr);
+    }
+}
+
+pub struct IntroSort;
+
+impl<T: PartialOrd> Sorter<T> for IntroSort {
+    fn name(&self) -> &'static str {
+        return "intro_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            worst: "O(n log n)",
+            ..Sorter::<T>::complexity(&QuickSortInPlace)
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        intro_sort(arr);
+    }
+}
+
+pub struct HeapSort;
+
+impl<T: PartialOrd> Sorter<T> for HeapSort {
+    fn name(&self) -> &'static str {
+        return "heap_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n log n)",
+            average: "O(n log n)",
+            worst: "O(n log n)",
+            space: "O(1)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        heap_sort(arr);
+    }
+}
+
+pub struct RadixSort;
+
+impl<T: RadixKey> Sorter<T> for RadixSort {
+    fn name(&self) -> &'static str {
+        return "radix_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return RADIX;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        lsd_radix_sort(arr);
+    }
+}
+
+/// Counting sort, not part of [`integer_sorters`] since its memory grows with the range of the
+/// keys rather than their number
+pub struct CountingSort;
+
+impl<T: RadixKey> Sorter<T> for CountingSort {
+    fn name(&self) -> &'static str {
+        return "counting_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n + k)",
+            average: "O(n + k)",
+            worst: "O(n + k)",
+            space: "O(n + k)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        counting_sort(arr);
+    }
+}
+
+/// Bucket sort, for keys that are uniformly distributed over their range
+pub struct BucketSort;
+
+impl<T: Into<f64> + PartialOrd + Copy> Sorter<T> for BucketSort {
+    fn name(&self) -> &'static str {
+        return "bucket_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n)",
+            average: "O(n)",
+            worst: "O(n²)",
+            space: "O(n)",
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        bucket_sort(arr);
+    }
+}
+
+pub struct MsdRadixSort;
+
+impl<T: AsRef<[u8]>> Sorter<T> for MsdRadixSort {
+    fn name(&self) -> &'static str {
+        return "msd_radix_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n)",
+            ..RADIX
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        msd_radix_sort(arr);
+    }
+}
+
+pub struct AmericanFlagSort;
+
+impl<T: AsRef<[u8]>> Sorter<T> for AmericanFlagSort {
+    fn name(&self) -> &'static str {
+        return "american_flag_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Complexity {
+            best: "O(n)",
+            space: "O(w)",
+            ..RADIX
+        };
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        american_flag_sort(arr);
+    }
+}
+
+pub struct ParallelMergeSort(pub ParallelConfig);
+
+impl<T: PartialOrd + Clone + Send + Sync> Sorter<T> for ParallelMergeSort {
+    fn name(&self) -> &'static str {
+        return "parallel_merge_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return Sorter::<T>::is_stable(&MergeSort);
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Sorter::<T>::complexity(&MergeSort);
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        let sorted = parallel_merge_sort(arr, &self.0);
+
+        arr.clone_from_slice(&sorted);
+    }
+}
+
+pub struct ParallelQuickSort(pub ParallelConfig);
+
+impl<T: PartialOrd + Send> Sorter<T> for ParallelQuickSort {
+    fn name(&self) -> &'static str {
+        return "parallel_quick_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return false;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return true;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return Sorter::<T>::complexity(&QuickSortInPlace);
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        parallel_quick_sort(arr, &self.0);
+    }
+}
+
+pub struct ParallelRadixSort(pub ParallelConfig);
+
+impl<T: RadixKey + Send + Sync> Sorter<T> for ParallelRadixSort {
+    fn name(&self) -> &'static str {
+        return "parallel_radix_sort";
+    }
+
+    fn is_stable(&self) -> bool {
+        return true;
+    }
+
+    fn has_quadratic_worst_case(&self) -> bool {
+        return false;
+    }
+
+    fn complexity(&self) -> Complexity {
+        return RADIX;
+    }
+
+    fn sort(&self, arr: &mut [T]) {
+        parallel_radix_sort(arr, &self.0);
+    }
+}
+
+/// Every algorithm that sorts by comparing elements
+///
+/// # Example
+/// ```
+/// let mut results = Vec::new();
+///
+/// for sorter in algorithms_exercises::sorter::comparison_sorters::<i32>() {
+///     let mut nums = Vec::from([3, 1, 2]);
+///
+///     sorter.sort(&mut nums);
+///     results.push((sorter.name(), nums));
+/// }
+///
+/// assert!(results.iter().all(|(_, nums)| *nums == [1, 2, 3]));
+/// ```
+pub fn comparison_sorters<T>() -> Vec<Box<dyn Sorter<T>>>
+where
+    T: PartialOrd + Clone + Send + Sync + 'static,
+{
+    return Vec::from([
+        Box::new(BubbleSort) as Box<dyn Sorter<T>>,
+        Box::new(CocktailShakerSort),
+        Box::new(GnomeSort),
+        Box::new(InsertionSort),
+        Box::new(BinaryInsertionSort),
+        Box::new(CombSort),
+        Box::new(ShellSort(GapSequence::Shell)),
+        Box::new(ShellSort(GapSequence::Knuth)),
+        Box::new(ShellSort(GapSequence::Ciura)),
+        Box::new(ShellSort(GapSequence::Sedgewick)),
+        Box::new(MergeSort),
+        Box::new(QuickSort),
+        Box::new(StableQuickSort),
+        Box::new(QuickSortInPlace),
+        Box::new(IntroSort),
+        Box::new(HeapSort),
+        Box::new(ParallelMergeSort(ParallelConfig::default())),
+        Box::new(ParallelQuickSort(ParallelConfig::default())),
+    ]);
+}
+
+/// [`comparison_sorters`] plus the LSD radix sorts, for integer (and float) keys
+pub fn integer_sorters<T>() -> Vec<Box<dyn Sorter<T>>>
+where
+    T: RadixKey + PartialOrd + Send + Sync + 'static,
+{
+    let mut sorters = comparison_sorters();
+
+    sorters.push(Box::new(RadixSort));
+    sorters.push(Box::new(ParallelRadixSort(ParallelConfig::default())));
+
+    return sorters;
+}
+
+/// [`comparison_sorters`] plus the MSD radix sorts, for byte-string keys
+///
+/// The radix sorts order keys by their bytes, which agrees with `PartialOrd` for `String`,
+/// `&str`, `Vec<u8>` and `&[u8]`.
+pub fn string_sorters<T>() -> Vec<Box<dyn Sorter<T>>>
+where
+    T: AsRef<[u8]> + PartialOrd + Clone + Send + Sync + 'static,
+{
+    let mut sorters = comparison_sorters();
+
+    sorters.push(Box::new(MsdRadixSort));
+    sorters.push(Box::new(AmericanFlagSort));
+
+    return sorters;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::{sorts_stably, Tagged};
+
+    fn assert_sorts<T>(sorters: Vec<Box<dyn Sorter<T>>>, input: &[T])
+    where
+        T: Ord + Clone + std::fmt::Debug,
+    {
+        let mut expected = input.to_vec();
+
+        expected.sort();
+
+        for sorter in sorters {
+            let mut arr = input.to_vec();
+
+            sorter.sort(&mut arr);
+            assert_eq!(arr, expected, "{}", sorter.name());
+        }
+    }
+
+    #[test]
+    fn test_sorters() {
+        let nums: Vec<i64> = Vec::from([5, -3, 8, 0, 8, -100, 42, 7, 7, 1]);
+        let words: Vec<String> = ["pear", "fig", "", "apple", "figs", "f"].iter().map(|w| w.to_string()).collect();
+
+        assert_sorts(comparison_sorters(), &['g', 'd', 'a', 'b', 'f']);
+        assert_sorts(integer_sorters(), &nums);
+        assert_sorts(integer_sorters::<u8>(), &[]);
+        assert_sorts(string_sorters(), &words);
+        assert_sorts(Vec::from([Box::new(CountingSort) as Box<dyn Sorter<i64>>]), &nums);
+        assert_sorts(Vec::from([Box::new(BucketSort) as Box<dyn Sorter<u32>>]), &[9, 4, 4, 0, 7]);
+    }
+
+    #[test]
+    fn test_sorter_metadata() {
+        let sorters = integer_sorters::<u32>();
+        let mut names: Vec<_> = sorters.iter().map(|sorter| sorter.name()).collect();
+        let count = names.len();
+
+        names.sort();
+        names.dedup();
+
+        assert_eq!(names.len(), count);
+        assert!(sorters.iter().all(|sorter| sorter.complexity().worst.starts_with("O(")));
+        assert!(Sorter::<u32>::is_stable(&RadixSort));
+        assert!(!Sorter::<u32>::is_stable(&QuickSort));
+        assert!(Sorter::<u32>::is_stable(&StableQuickSort));
+        assert!(Sorter::<u32>::has_quadratic_worst_case(&QuickSortInPlace));
+        assert!(!Sorter::<u32>::has_quadratic_worst_case(&IntroSort));
+        assert!(Sorter::<u32>::has_quadratic_worst_case(&ShellSort(GapSequence::Shell)));
+        assert!(!Sorter::<u32>::has_quadratic_worst_case(&ShellSort(GapSequence::Knuth)));
+        assert!(Sorter::<u32>::has_quadratic_worst_case(&ShellSort(GapSequence::Ciura)));
+
+        for sorter in sorters.iter() {
+            assert_eq!(sorter.has_quadratic_worst_case(), sorter.complexity().worst == "O(n²)", "{}", sorter.name());
+        }
+        assert_eq!(Sorter::<String>::complexity(&AmericanFlagSort).space, "O(w)");
+    }
+
+    #[test]
+    fn test_sorter_stability() {
+        let keys = Vec::from([3, 1, 3, 0, 1, 3, 2, 0, 1, 1, 2, 3, 0, 0, 2, 1, 3]);
+        let stable: Vec<_> = integer_sorters::<Tagged>()
+            .iter()
+            .filter(|sorter| sorter.is_stable())
+            .map(|sorter| sorter.name())
+            .collect();
+
+        assert_eq!(
+            stable,
+            [
+                "bubble_sort",
+                "cocktail_shaker_sort",
+                "gnome_sort",
+                "insertion_sort",
+                "binary_insertion_sort",
+                "merge_sort",
+                "stable_quick_sort",
+                "parallel_merge_sort",
+                "radix_sort",
+                "parallel_radix_sort",
+            ]
+        );
+
+        for sorter in integer_sorters::<Tagged>() {
+            if sorter.is_stable() {
+                assert!(sorts_stably(sorter.as_ref(), &keys), "{}", sorter.name());
+            }
+        }
+
+        assert!(sorts_stably(&CountingSort, &keys));
+        // Elements equal to the last element are moved behind it
+        assert!(!sorts_stably(&QuickSort, &[1, 1, 0]));
+    }
+}
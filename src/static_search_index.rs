@@ -0,0 +1,179 @@
+/// How many levels below the current node [`StaticSearchIndex`] prefetches: the 16 descendants
+/// four levels down are 16 consecutive elements, a cache line or two
+const PREFETCH_LEVELS: u32 = 4;
+
+/// Fills `order` with the index in the sorted slice of every node `k` (1-based) of the subtree
+/// rooted at `k`, visiting the nodes in order
+fn fill_order(order: &mut [usize], k: usize, next: &mut usize) {
+    if k > order.len() {
+        return;
+    }
+
+    fill_order(order, 2 * k, next);
+    order[k - 1] = *next;
+    *next += 1;
+    fill_order(order, 2 * k + 1, next);
+}
+
+/// Hints the CPU to start loading `ptr` into the cache, never faults even for invalid addresses
+#[inline(always)]
+fn prefetch<T>(ptr: *const T) {
+    #[cfg(target_arch = "x86_64")]
+    // SAFETY: SSE is part of every x86_64 target, and a prefetch only hints the cache without
+    // reading through `ptr`, so it cannot fault. The callers derive `ptr` from the tree's slice
+    // with `wrapping_add`, which may point past its end but never reads there
+    unsafe {
+        use std::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+        _mm_prefetch::<_MM_HINT_T0>(ptr as *const i8);
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    let _ = ptr;
+}
+
+/// Read-only sorted set that is searched faster than a sorted slice once it outgrows the cache
+///
+/// Stores the elements in Eytzinger layout, the order of a breadth-first walk of a complete
+/// binary search tree: the root first, then both of its children, and the children of node `k`
+/// (counting from 1) at `2k` and `2k + 1`. The first few levels that every search goes through
+/// share a handful of cache lines, and the next nodes a search might visit are adjacent, so they
+/// can be prefetched while the current comparison is made. The search loop has no data dependent
+/// branches either.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::static_search_index::StaticSearchIndex;
+///
+/// let index = StaticSearchIndex::new(&[2, 3, 5, 7, 11, 13], false);
+///
+/// assert!(index.contains(&7));
+/// assert_eq!(index.lower_bound(&8), Some(&11));
+/// assert_eq!(index.rank(&8), 4);
+/// ```
+pub struct StaticSearchIndex<T> {
+    /// Elements in Eytzinger order, node `k` at index `k - 1`
+    tree: Vec<T>,
+    /// Index in the sorted slice of every element of `tree`
+    ranks: Vec<usize>,
+    prefetch: bool,
+}
+
+impl<T: Ord + Clone> StaticSearchIndex<T> {
+    /// Builds an index over the elements of `sorted`, which must be sorted in ascending order
+    ///
+    /// With `prefetch` every search step asks the CPU to load the nodes four levels further down,
+    /// which helps when the index is much larger than the cache and costs a little when it isn't.
+    pub fn new(sorted: &[T], prefetch: bool) -> Self {
+        let mut ranks = vec![0; sorted.len()];
+
+        fill_order(&mut ranks, 1, &mut 0);
+
+        return Self {
+            tree: ranks.iter().map(|&i| sorted[i].clone()).collect(),
+            ranks,
+            prefetch,
+        };
+    }
+}
+
+impl<T: Ord> StaticSearchIndex<T> {
+    pub fn len(&self) -> usize {
+        return self.tree.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.tree.is_empty();
+    }
+
+    /// Node (counting from 1) of the first element that is not less than `target`, or 0 if there
+    /// is none
+    fn search(&self, target: &T) -> usize {
+        let mut k = 1;
+
+        while k <= self.tree.len() {
+            if self.prefetch {
+                prefetch(self.tree.as_ptr().wrapping_add((k << PREFETCH_LEVELS) - 1));
+            }
+
+            k = 2 * k + usize::from(self.tree[k - 1] < *target);
+        }
+
+        // Every right turn appended a 1 bit. Dropping them and the last left turn gives the last
+        // node the search went left at, i.e. the last node that was not less than `target`
+        return k >> (k.trailing_ones() + 1);
+    }
+
+    pub fn contains(&self, target: &T) -> bool {
+        return self.lower_bound(target) == Some(target);
+    }
+
+    /// The first element that is not less than `target`
+    pub fn lower_bound(&self, target: &T) -> Option<&T> {
+        let k = self.search(target);
+
+        return if k == 0 { None } else { Some(&self.tree[k - 1]) };
+    }
+
+    /// Number of elements less than `target`, which is its index in the sorted slice if present
+    pub fn rank(&self, target: &T) -> usize {
+        let k = self.search(target);
+
+        return if k == 0 { self.len() } else { self.ranks[k - 1] };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary_search::lower_bound;
+
+    #[test]
+    fn test_new() {
+        let index = StaticSearchIndex::new(&[1, 2, 3, 4, 5, 6], false);
+
+        assert_eq!(index.tree, [4, 2, 6, 1, 3, 5]);
+        assert_eq!(index.ranks, [3, 1, 5, 0, 2, 4]);
+        assert_eq!(index.len(), 6);
+        assert!(StaticSearchIndex::<u8>::new(&[], false).is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let sorted: Vec<u32> = (0..1000).map(|n| n * 3).collect();
+
+        for prefetch in [false, true] {
+            let index = StaticSearchIndex::new(&sorted, prefetch);
+
+            for target in 0..3001 {
+                assert_eq!(index.contains(&target), target % 3 == 0 && target < 3000, "{}", target);
+            }
+        }
+
+        assert!(!StaticSearchIndex::new(&[], true).contains(&1));
+    }
+
+    #[test]
+    fn test_lower_bound() {
+        for len in 0..70 {
+            let sorted: Vec<u32> = (0..len).map(|n| n / 3 * 2).collect();
+            let index = StaticSearchIndex::new(&sorted, true);
+
+            for target in 0..len {
+                assert_eq!(index.lower_bound(&target), sorted.get(lower_bound(&sorted, &target)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rank() {
+        for len in 0..70 {
+            let sorted: Vec<u32> = (0..len).map(|n| n / 3 * 2).collect();
+            let index = StaticSearchIndex::new(&sorted, false);
+
+            for target in 0..len + 2 {
+                assert_eq!(index.rank(&target), lower_bound(&sorted, &target), "len={} target={}", len, target);
+            }
+        }
+    }
+}
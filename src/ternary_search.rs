@@ -0,0 +1,163 @@
+use std::ops::Range;
+
+/// Iteration cap for the float searches, each iteration shrinks the interval to 2/3
+const MAX_FLOAT_ITERATIONS: usize = 200;
+
+fn ternary_search_int<T, F, B>(range: Range<i64>, mut f: F, better: B) -> Option<i64>
+where
+    T: PartialOrd,
+    F: FnMut(i64) -> T,
+    B: Fn(&T, &T) -> bool,
+{
+    let (mut min, mut max) = (range.start, range.end);
+
+    if min >= max {
+        return None;
+    }
+
+    // The width is computed in i128 so that ranges spanning all of i64 don't overflow
+    while max as i128 - min as i128 > 3 {
+        let third = ((max as i128 - min as i128) / 3) as i64;
+        let (m1, m2) = (min + third, max - 1 - third);
+
+        if better(&f(m1), &f(m2)) {
+            max = m2;
+        } else {
+            min = m1 + 1;
+        }
+    }
+
+    let mut best = min;
+    let mut best_value = f(min);
+
+    for x in min + 1..max {
+        let value = f(x);
+
+        if better(&value, &best_value) {
+            best = x;
+            best_value = value;
+        }
+    }
+
+    return Some(best);
+}
+
+fn ternary_search_float<T, F, B>(min: f64, max: f64, epsilon: f64, mut f: F, better: B) -> f64
+where
+    T: PartialOrd,
+    F: FnMut(f64) -> T,
+    B: Fn(&T, &T) -> bool,
+{
+    let (mut min, mut max) = (min, max);
+
+    for _ in 0..MAX_FLOAT_ITERATIONS {
+        if max - min <= epsilon {
+            break;
+        }
+
+        let third = (max - min) / 3.0;
+        let (m1, m2) = (min + third, max - third);
+
+        if better(&f(m1), &f(m2)) {
+            max = m2;
+        } else {
+            min = m1;
+        }
+    }
+
+    return min + (max - min) / 2.0;
+}
+
+/// Argument in `range` where `f` is smallest, or `None` when `range` is empty
+///
+/// `f` must be strictly unimodal over `range`: strictly decreasing up to its minimum and strictly
+/// increasing after it (flat stretches other than at the minimum can mislead the search). Each
+/// step evaluates `f` twice and discards a third of the range, so it takes O(log n) calls.
+///
+/// # Example
+/// ```
+/// let argmin = algorithms_exercises::ternary_search::ternary_search_min(-100..100, |x| (x - 17) * (x - 17));
+///
+/// assert_eq!(argmin, Some(17));
+/// ```
+pub fn ternary_search_min<T, F>(range: Range<i64>, f: F) -> Option<i64>
+where
+    T: PartialOrd,
+    F: FnMut(i64) -> T,
+{
+    return ternary_search_int(range, f, |a, b| a < b);
+}
+
+/// Argument in `range` where the strictly unimodal `f` is largest, see [`ternary_search_min`]
+pub fn ternary_search_max<T, F>(range: Range<i64>, f: F) -> Option<i64>
+where
+    T: PartialOrd,
+    F: FnMut(i64) -> T,
+{
+    return ternary_search_int(range, f, |a, b| a > b);
+}
+
+/// Argument between `min` and `max` where the unimodal `f` is smallest, within `epsilon`
+///
+/// Stops once the interval is at most `epsilon` wide, or after 200 iterations (which narrows any
+/// finite interval down to the precision of an `f64`), and returns its middle.
+///
+/// # Example
+/// ```
+/// let argmin = algorithms_exercises::ternary_search::ternary_search_min_f64(0.0, 3.0, 1e-9, |x: f64| (x - 1.5).powi(2));
+///
+/// assert!((argmin - 1.5).abs() < 1e-6);
+/// ```
+pub fn ternary_search_min_f64<T, F>(min: f64, max: f64, epsilon: f64, f: F) -> f64
+where
+    T: PartialOrd,
+    F: FnMut(f64) -> T,
+{
+    return ternary_search_float(min, max, epsilon, f, |a, b| a < b);
+}
+
+/// Argument between `min` and `max` where the unimodal `f` is largest, within `epsilon`, see
+/// [`ternary_search_min_f64`]
+pub fn ternary_search_max_f64<T, F>(min: f64, max: f64, epsilon: f64, f: F) -> f64
+where
+    T: PartialOrd,
+    F: FnMut(f64) -> T,
+{
+    return ternary_search_float(min, max, epsilon, f, |a, b| a > b);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ternary_search_min() {
+        for target in -20..20 {
+            assert_eq!(ternary_search_min(-10..10, |x| (x - target).abs()), Some(target.clamp(-10, 9)));
+        }
+
+        assert_eq!(ternary_search_min(5..6, |x| x), Some(5));
+        assert_eq!(ternary_search_min(5..5, |x| x), None);
+        assert_eq!(ternary_search_min(i64::MIN..i64::MAX, |x| x.abs()), Some(0));
+    }
+
+    #[test]
+    fn test_ternary_search_max() {
+        let heights = [1, 4, 9, 12, 30, 22, 8, 3];
+
+        assert_eq!(ternary_search_max(0..heights.len() as i64, |i| heights[i as usize]), Some(4));
+        assert_eq!(ternary_search_max(0..3, |i| heights[i as usize]), Some(2));
+        assert_eq!(ternary_search_max(0..0, |i| i), None);
+    }
+
+    #[test]
+    fn test_ternary_search_f64() {
+        let argmin = ternary_search_min_f64(-10.0, 10.0, 1e-9, |x: f64| (x + 2.5).powi(2));
+        let argmax = ternary_search_max_f64(0.0, std::f64::consts::PI, 1e-9, f64::sin);
+        let exact = ternary_search_min_f64(-1.0, 1.0, 0.0, f64::abs);
+
+        assert!((argmin + 2.5).abs() < 1e-6);
+        assert!((argmax - std::f64::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!(exact.abs() < 1e-12);
+    }
+}
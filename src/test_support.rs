@@ -0,0 +1,503 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::radix_sort::RadixKey;
+use crate::sorter::Sorter;
+
+/// Small deterministic xorshift64* generator, so inputs can be reproduced from their seed
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // Spread the seed with a splitmix64 step, xorshift must not start from 0
+        let mut z = seed.wrapping_add(0x9e3779b97f4a7c15);
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+
+        return Self {
+            state: (z ^ (z >> 31)) | 1,
+        };
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+
+        return self.state.wrapping_mul(0x2545f4914f6cdd1d);
+    }
+
+    /// Uniform value in `0..bound`, `bound` must not be 0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        return ((self.next_u64() as u128 * bound as u128) >> 64) as u64;
+    }
+}
+
+/// Shapes of input the sorts are tested and benchmarked against
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Distribution {
+    /// Uniformly random values over the whole `u64` range
+    Random,
+    Sorted,
+    ReverseSorted,
+    /// Random values drawn from only 8 distinct keys
+    FewUnique,
+    /// Ascending up to the middle, then descending
+    OrganPipe,
+    /// Repeated ascending runs of about `sqrt(len)` values
+    Sawtooth,
+}
+
+impl Distribution {
+    pub const ALL: [Distribution; 6] = [
+        Distribution::Random,
+        Distribution::Sorted,
+        Distribution::ReverseSorted,
+        Distribution::FewUnique,
+        Distribution::OrganPipe,
+        Distribution::Sawtooth,
+    ];
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Distribution::Random => "random",
+            Distribution::Sorted => "sorted",
+            Distribution::ReverseSorted => "reverse_sorted",
+            Distribution::FewUnique => "few_unique",
+            Distribution::OrganPipe => "organ_pipe",
+            Distribution::Sawtooth => "sawtooth",
+        };
+
+        return write!(f, "{}", name);
+    }
+}
+
+/// `len` values of the given shape, the same `seed` always gives the same values
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::test_support::{generate, Distribution};
+///
+/// assert_eq!(generate(Distribution::OrganPipe, 6, 0), [0, 1, 2, 3, 2, 1]);
+/// assert_eq!(generate(Distribution::Random, 4, 7), generate(Distribution::Random, 4, 7));
+/// ```
+pub fn generate(distribution: Distribution, len: usize, seed: u64) -> Vec<u64> {
+    let mut rng = Rng::new(seed);
+
+    return match distribution {
+        Distribution::Random => (0..len).map(|_| rng.next_u64()).collect(),
+        Distribution::Sorted | Distribution::ReverseSorted => {
+            let mut values: Vec<u64> = (0..len).map(|_| rng.below(len as u64 * 4 + 1)).collect();
+
+            values.sort();
+
+            if distribution == Distribution::ReverseSorted {
+                values.reverse();
+            }
+
+            values
+        }
+        Distribution::FewUnique => (0..len).map(|_| rng.below(8)).collect(),
+        Distribution::OrganPipe => (0..len).map(|i| i.min(len - i) as u64).collect(),
+        Distribution::Sawtooth => {
+            let tooth = ((len as f64).sqrt() as usize).max(1);
+
+            (0..len).map(|i| (i % tooth) as u64).collect()
+        }
+    };
+}
+
+/// Element ordered only by `key`, `tag` records its position before sorting
+///
+/// Sorting tagged elements with a stable algorithm leaves equal keys in increasing tag order.
+#[derive(Clone, Copy, Debug)]
+pub struct Tagged {
+    pub key: u64,
+    pub tag: usize,
+}
+
+impl PartialEq for Tagged {
+    fn eq(&self, other: &Self) -> bool {
+        return self.key == other.key;
+    }
+}
+
+impl Eq for Tagged {}
+
+impl PartialOrd for Tagged {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        return Some(self.cmp(other));
+    }
+}
+
+impl Ord for Tagged {
+    fn cmp(&self, other: &Self) -> Ordering {
+        return self.key.cmp(&other.key);
+    }
+}
+
+impl RadixKey for Tagged {
+    const BYTES: usize = u64::BYTES;
+
+    fn radix_byte(&self, n: usize) -> u8 {
+        return self.key.radix_byte(n);
+    }
+}
+
+/// Whether `sorter` sorts `input` the same way `slice::sort` does
+pub fn sorts_like_oracle(sorter: &dyn Sorter<u64>, input: &[u64]) -> bool {
+    let mut actual = input.to_vec();
+    let mut expected = input.to_vec();
+
+    sorter.sort(&mut actual);
+    expected.sort();
+
+    return actual == expected;
+}
+
+/// `keys` tagged with their positions
+pub fn tag(keys: &[u64]) -> Vec<Tagged> {
+    return keys.iter().enumerate().map(|(tag, &key)| Tagged { key, tag }).collect();
+}
+
+/// Whether `arr` is sorted by key with equal keys in increasing tag order
+pub fn is_stably_sorted(arr: &[Tagged]) -> bool {
+    return arr
+        .windows(2)
+        .all(|w| w[0].key < w[1].key || (w[0].key == w[1].key && w[0].tag < w[1].tag));
+}
+
+/// Whether `sorter` keeps elements with equal `keys` in their original order
+pub fn sorts_stably(sorter: &dyn Sorter<Tagged>, keys: &[u64]) -> bool {
+    let mut tagged = tag(keys);
+
+    sorter.sort(&mut tagged);
+
+    return is_stably_sorted(&tagged);
+}
+
+/// Smallest input derived from `input` for which `fails` still returns true
+///
+/// Repeatedly removes chunks of elements (halves, then quarters, down to single elements) and
+/// then bisects each value towards 0, keeping every change that still fails.
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::test_support::shrink;
+///
+/// // "Fails" whenever some value is at least 10
+/// let minimal = shrink(&[3, 50, 7, 12, 1], |input| input.iter().any(|&v| v >= 10));
+///
+/// assert_eq!(minimal, [10]);
+/// ```
+pub fn shrink<F>(input: &[u64], fails: F) -> Vec<u64>
+where
+    F: Fn(&[u64]) -> bool,
+{
+    let mut current = input.to_vec();
+    let mut chunk = current.len() / 2;
+
+    while chunk >= 1 {
+        let mut start = 0;
+        let mut removed = false;
+
+        while start < current.len() {
+            let end = (start + chunk).min(current.len());
+            let candidate: Vec<u64> = current[..start].iter().chain(&current[end..]).copied().collect();
+
+            if fails(&candidate) {
+                current = candidate;
+                removed = true;
+            } else {
+                start += chunk;
+            }
+        }
+
+        if !removed {
+            chunk /= 2;
+        }
+    }
+
+    // Shrinking one value can let another one get smaller, so repeat until nothing changes
+    let mut changed = true;
+
+    while changed {
+        changed = false;
+
+        for i in 0..current.len() {
+            // Bisect for the smallest replacement that still fails, which finds the minimum
+            // whenever every value above it fails too
+            let mut low = 0;
+            let mut high = current[i];
+
+            while low < high {
+                let mid = low + (high - low) / 2;
+                let mut candidate = current.clone();
+
+                candidate[i] = mid;
+
+                if fails(&candidate) {
+                    high = mid;
+                } else {
+                    low = mid + 1;
+                }
+            }
+
+            if high < current[i] {
+                current[i] = high;
+                changed = true;
+            }
+        }
+    }
+
+    return current;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureKind {
+    Unsorted,
+    Unstable,
+}
+
+/// A shrunk input on which a sorter disagreed with the oracle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Counterexample {
+    pub sorter: &'static str,
+    pub kind: FailureKind,
+    pub distribution: Distribution,
+    pub seed: u64,
+    /// Length of the generated input before shrinking
+    pub len: usize,
+    pub input: Vec<u64>,
+}
+
+impl fmt::Display for Counterexample {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let problem = match self.kind {
+            FailureKind::Unsorted => "did not sort",
+            FailureKind::Unstable => "reordered equal keys in",
+        };
+
+        return write!(
+            f,
+            "{} {} {:?} (shrunk from {} input of length {}, seed {})",
+            self.sorter, problem, self.input, self.distribution, self.len, self.seed
+        );
+    }
+}
+
+/// Input lengths and seeds to generate for every distribution
+pub struct TestPlan {
+    pub lens: Vec<usize>,
+    pub seeds: Vec<u64>,
+}
+
+impl Default for TestPlan {
+    fn default() -> Self {
+        return Self {
+            lens: Vec::from([0, 1, 2, 3, 5, 10, 17, 64, 100, 257]),
+            seeds: Vec::from([0, 1, 2]),
+        };
+    }
+}
+
+/// First generated input (with every value passed through `map`) that `fails`, shrunk
+fn find_failure<M, F>(plan: &TestPlan, map: M, fails: F) -> Option<(Distribution, u64, usize, Vec<u64>)>
+where
+    M: Fn(u64) -> u64,
+    F: Fn(&[u64]) -> bool,
+{
+    for distribution in Distribution::ALL {
+        for &len in &plan.lens {
+            for &seed in &plan.seeds {
+                let input: Vec<u64> = generate(distribution, len, seed).into_iter().map(&map).collect();
+
+                if fails(&input) {
+                    return Some((distribution, seed, len, shrink(&input, &fails)));
+                }
+            }
+        }
+    }
+
+    return None;
+}
+
+/// Runs every sorter against `slice::sort` on every input of the plan
+///
+/// # Example
+/// ```
+/// use algorithms_exercises::{sorter::integer_sorters, test_support::{differential_test, TestPlan}};
+///
+/// assert_eq!(differential_test(&integer_sorters(), &TestPlan::default()), Ok(()));
+/// ```
+pub fn differential_test(sorters: &[Box<dyn Sorter<u64>>], plan: &TestPlan) -> Result<(), Counterexample> {
+    for sorter in sorters {
+        if let Some((distribution, seed, len, input)) =
+            find_failure(plan, |value| value, |input| !sorts_like_oracle(sorter.as_ref(), input))
+        {
+            return Err(Counterexample {
+                sorter: sorter.name(),
+                kind: FailureKind::Unsorted,
+                distribution,
+                seed,
+                len,
+                input,
+            });
+        }
+    }
+
+    return Ok(());
+}
+
+/// Checks that every sorter claiming [`Sorter::is_stable`] keeps equal keys in order
+///
+/// Keys are reduced modulo 16 so that every distribution has plenty of ties.
+pub fn stability_test(sorters: &[Box<dyn Sorter<Tagged>>], plan: &TestPlan) -> Result<(), Counterexample> {
+    for sorter in sorters.iter().filter(|sorter| sorter.is_stable()) {
+        let fails = |keys: &[u64]| !sorts_stably(sorter.as_ref(), keys);
+
+        if let Some((distribution, seed, len, input)) = find_failure(plan, |key| key % 16, fails) {
+            return Err(Counterexample {
+                sorter: sorter.name(),
+                kind: FailureKind::Unstable,
+                distribution,
+                seed,
+                len,
+                input,
+            });
+        }
+    }
+
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sorter::{integer_sorters, Complexity};
+
+    /// Sorts everything but the last element
+    struct SortsAllButLast;
+
+    impl Sorter<u64> for SortsAllButLast {
+        fn name(&self) -> &'static str {
+            return "sorts_all_but_last";
+        }
+
+        fn is_stable(&self) -> bool {
+            return true;
+        }
+
+        fn has_quadratic_worst_case(&self) -> bool {
+            return false;
+        }
+
+        fn complexity(&self) -> Complexity {
+            return Complexity {
+                best: "O(n log n)",
+                average: "O(n log n)",
+                worst: "O(n log n)",
+                space: "O(1)",
+            };
+        }
+
+        fn sort(&self, arr: &mut [u64]) {
+            if let Some((_, rest)) = arr.split_last_mut() {
+                rest.sort();
+            }
+        }
+    }
+
+    /// Claims to be stable but reverses runs of equal keys
+    struct Reversing;
+
+    impl Sorter<Tagged> for Reversing {
+        fn name(&self) -> &'static str {
+            return "reversing";
+        }
+
+        fn is_stable(&self) -> bool {
+            return true;
+        }
+
+        fn has_quadratic_worst_case(&self) -> bool {
+            return false;
+        }
+
+        fn complexity(&self) -> Complexity {
+            return Sorter::<u64>::complexity(&SortsAllButLast);
+        }
+
+        fn sort(&self, arr: &mut [Tagged]) {
+            arr.sort_by(|a, b| a.key.cmp(&b.key).then(b.tag.cmp(&a.tag)));
+        }
+    }
+
+    #[test]
+    fn test_rng() {
+        let mut a = Rng::new(0);
+        let mut b = Rng::new(0);
+        let mut c = Rng::new(1);
+
+        assert_eq!(a.next_u64(), b.next_u64());
+        assert_ne!(a.next_u64(), c.next_u64());
+        assert!((0..1000).all(|_| a.below(10) < 10));
+    }
+
+    #[test]
+    fn test_generate() {
+        for distribution in Distribution::ALL {
+            assert_eq!(generate(distribution, 0, 1), []);
+            assert_eq!(generate(distribution, 50, 1).len(), 50);
+            assert_eq!(generate(distribution, 50, 1), generate(distribution, 50, 1));
+        }
+
+        let sorted = generate(Distribution::Sorted, 100, 3);
+        let reversed = generate(Distribution::ReverseSorted, 100, 3);
+
+        assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        assert!(reversed.windows(2).all(|w| w[0] >= w[1]));
+        assert!(generate(Distribution::FewUnique, 100, 3).iter().all(|&v| v < 8));
+        assert_eq!(generate(Distribution::OrganPipe, 5, 0), [0, 1, 2, 2, 1]);
+        assert_eq!(generate(Distribution::Sawtooth, 9, 0), [0, 1, 2, 0, 1, 2, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_shrink() {
+        let fails = |input: &[u64]| !sorts_like_oracle(&SortsAllButLast, input);
+
+        assert_eq!(shrink(&generate(Distribution::Random, 100, 5), fails), [1, 0]);
+        assert_eq!(shrink(&[9, 9, 9], |input| input.len() >= 2), [0, 0]);
+    }
+
+    #[test]
+    fn test_differential_test() {
+        let plan = TestPlan::default();
+        let broken: Vec<Box<dyn Sorter<u64>>> = Vec::from([Box::new(SortsAllButLast) as Box<dyn Sorter<u64>>]);
+        let counterexample = differential_test(&broken, &plan).unwrap_err();
+
+        assert_eq!(differential_test(&integer_sorters(), &plan), Ok(()));
+        assert_eq!(counterexample.sorter, "sorts_all_but_last");
+        assert_eq!(counterexample.kind, FailureKind::Unsorted);
+        assert_eq!(counterexample.input, [1, 0]);
+        assert!(counterexample.to_string().starts_with("sorts_all_but_last did not sort [1, 0]"));
+    }
+
+    #[test]
+    fn test_stability_test() {
+        let plan = TestPlan::default();
+        let broken: Vec<Box<dyn Sorter<Tagged>>> = Vec::from([Box::new(Reversing) as Box<dyn Sorter<Tagged>>]);
+        let counterexample = stability_test(&broken, &plan).unwrap_err();
+
+        assert_eq!(stability_test(&integer_sorters(), &plan), Ok(()));
+        assert_eq!(counterexample.kind, FailureKind::Unstable);
+        // Values can only shrink one at a time, and changing either one breaks the tie
+        assert_eq!(counterexample.input.len(), 2);
+        assert_eq!(counterexample.input[0], counterexample.input[1]);
+    }
+}